@@ -0,0 +1,22 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use matchstick::equation::Equation;
+use matchstick::puzzle::{Puzzle, riddle::Riddle};
+
+// Several positions of "12+3=45" have multiple transition targets
+// so many symbol combinations are formed for each transition sequence
+fn solve_seven_symbols_with_two_movements(c: &mut Criterion) {
+    let riddle_equation: Equation = "12+3=45".parse().unwrap();
+
+    c.bench_function("solve 7 symbols with 2 movements", |b| {
+        b.iter(|| {
+            let mut puzzle =
+                Puzzle::new_from_riddle(Riddle::new(black_box(riddle_equation.clone()), 2));
+            puzzle.search_and_set_solution()
+        })
+    });
+}
+
+criterion_group!(benches, solve_seven_symbols_with_two_movements);
+criterion_main!(benches);
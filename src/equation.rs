@@ -1,11 +1,42 @@
-use evalexpr::eval_int;
-use itertools::Itertools;
+pub mod encoding;
+mod equation_pattern;
+pub mod evaluation;
+pub mod parse;
+pub mod validation;
 
-use crate::symbol::{Symbol, SymbolFilter};
-use crate::transition::TransitionSequence;
+use std::iter::{self, Peekable};
+use std::str::Chars;
 
-/// Holds list of [`Symbol`]s to represent a mathematical equation (or expression)
+use itertools::{Either, Itertools};
+
+use crate::segment_display::SegmentDisplay;
+use crate::symbol::Symbol;
+use crate::transition::{TransitionSequence, TransitionSequenceCache};
+pub use equation_pattern::{EquationPattern, PatternError};
+use evaluation::{Evaluator, IntEvaluator};
+use validation::{ValidationError, ValidationOptions};
+
+/// The index is outside of the [`Symbol`]s of an [`Equation`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexError {
+    /// The requested index
+    pub index: usize,
+    /// How many symbols the equation consists of
+    pub number_symbols: usize,
+}
+
+/// Reasons why [`Symbol`]s don't form a well-formed [`Equation`]
 #[derive(Clone, Debug, PartialEq)]
+pub enum EquationError {
+    /// There are no symbols at all
+    Empty,
+    /// Numbers and operators don't alternate, e.g. "2+=2"
+    BrokenAlternation,
+}
+
+/// Holds list of [`Symbol`]s to represent a mathematical equation (or expression) \
+/// [`Equation`]s are ordered by comparing their [`Symbol`]s lexicographically
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Equation {
     symbols: Vec<Symbol>,
 }
@@ -43,52 +74,789 @@ impl Equation {
         Equation { symbols }
     }
 
+    /// Like [`Equation::new_from_symbols`] but the order of the [`Symbol`]s is checked,
+    /// so equations that can never be valid are rejected right away \
+    /// Numbers and operators must alternate, starting and ending with a number.
+    /// A negation may only start an expression, e.g. "-3+5=2" or "2=-3+5",
+    /// and decorative symbols must directly follow a number.
+    /// Whether the [`Equation`] is mathematically true is not checked
+    /// ```
+    /// # use matchstick::equation::{Equation, EquationError};
+    /// # use matchstick::symbol::Symbol;
+    /// let symbols = vec![Symbol::Two, Symbol::Plus, Symbol::Equal, Symbol::Two];
+    /// assert_eq!(Err(EquationError::BrokenAlternation), Equation::try_new(symbols));
+    ///
+    /// // "-2 + 3 = 6" is well-formed, although false
+    /// let symbols = vec![
+    ///     Symbol::Minus,
+    ///     Symbol::Two,
+    ///     Symbol::Plus,
+    ///     Symbol::Three,
+    ///     Symbol::Equal,
+    ///     Symbol::Six,
+    /// ];
+    /// assert!(Equation::try_new(symbols).is_ok());
+    /// ```
+    pub fn try_new(symbols: Vec<Symbol>) -> Result<Self, EquationError> {
+        if symbols.is_empty() {
+            return Err(EquationError::Empty);
+        }
+
+        let equation = Equation { symbols };
+        let is_well_formed = equation
+            .without_decorations()
+            .is_some_and(|equation| equation.has_strict_alternation());
+        match is_well_formed {
+            true => Ok(equation),
+            false => Err(EquationError::BrokenAlternation),
+        }
+    }
+
+    /// All [`Equation`]s consisting of `number_symbols` arbitrary [`Symbol`]s \
+    /// Unlike an [`EquationPattern`] no restriction per position is needed.
+    /// The equations are formed one after another as the number grows exponentially
+    /// with the length, starting with the [`Symbol`]s in declaration order
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let number_symbols = Symbol::get_all().len();
+    /// assert_eq!(number_symbols.pow(2), Equation::all_of_length(2).count());
+    ///
+    /// let first_equation = Equation::all_of_length(3).next().unwrap();
+    /// assert_eq!("---", first_equation.canonical_string());
+    /// ```
+    pub fn all_of_length(number_symbols: usize) -> impl Iterator<Item = Equation> {
+        iter::repeat_n(Symbol::get_all(), number_symbols)
+            .map(|all_symbols| all_symbols.into_iter())
+            .multi_cartesian_product()
+            .map(Equation::new_from_symbols)
+    }
+
+    /// Like [`Equation::all_of_length`] but only [`Equation`]s with exactly one equal sign
+    /// are formed, e.g. as candidates for riddle equations
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// // the equal sign is at one of three positions, the others are any remaining symbol
+    /// let number_other_symbols = Symbol::get_all().len() - 1;
+    /// assert_eq!(
+    ///     3 * number_other_symbols.pow(2),
+    ///     Equation::all_of_length_with_one_equal_sign(3).count()
+    /// );
+    /// ```
+    pub fn all_of_length_with_one_equal_sign(
+        number_symbols: usize,
+    ) -> impl Iterator<Item = Equation> {
+        Equation::all_of_length(number_symbols).filter(|equation| {
+            equation
+                .symbols
+                .iter()
+                .filter(|symbol| **symbol == Symbol::Equal)
+                .count()
+                == 1
+        })
+    }
+
+    /// Getter function for [`Symbol`]s
+    pub fn get_symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// The [`Symbol`] at the given index, `None` if the index is out of bounds
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation: Equation = "2=2".parse().unwrap();
+    /// assert_eq!(Some(&Symbol::Equal), equation.get_symbol_at(1));
+    /// assert_eq!(None, equation.get_symbol_at(3));
+    /// ```
+    pub fn get_symbol_at(&self, index: usize) -> Option<&Symbol> {
+        self.symbols.get(index)
+    }
+
+    /// Replaces the [`Symbol`] at the given index in place
+    /// ```
+    /// # use matchstick::equation::{Equation, IndexError};
+    /// # use matchstick::symbol::Symbol;
+    /// let mut equation: Equation = "2=3".parse().unwrap();
+    /// assert_eq!(Ok(()), equation.replace_symbol_at(2, Symbol::Two));
+    /// assert_eq!(Ok(()), equation.mathematically_validate());
+    ///
+    /// assert_eq!(
+    ///     Err(IndexError {
+    ///         index: 3,
+    ///         number_symbols: 3
+    ///     }),
+    ///     equation.replace_symbol_at(3, Symbol::Two)
+    /// );
+    /// ```
+    pub fn replace_symbol_at(&mut self, index: usize, symbol: Symbol) -> Result<(), IndexError> {
+        let number_symbols = self.symbols.len();
+        match self.symbols.get_mut(index) {
+            Some(replaced_symbol) => {
+                *replaced_symbol = symbol;
+                Ok(())
+            }
+            None => Err(IndexError {
+                index,
+                number_symbols,
+            }),
+        }
+    }
+
+    /// Consumes the [`Equation`] and hands over its [`Symbol`]s
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation: Equation = "2=2".parse().unwrap();
+    /// assert_eq!(
+    ///     vec![Symbol::Two, Symbol::Equal, Symbol::Two],
+    ///     equation.into_symbols()
+    /// );
+    /// ```
+    pub fn into_symbols(self) -> Vec<Symbol> {
+        self.symbols
+    }
+
+    /// Number of matchsticks the [`Equation`] is built from
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "1+7=8".parse().unwrap();
+    /// assert_eq!(2 + 2 + 3 + 2 + 7, equation.matchstick_count());
+    /// ```
+    pub fn matchstick_count(&self) -> usize {
+        self.symbols.iter().map(Symbol::matchstick_count).sum()
+    }
+
+    /// Number of digits in the [`Equation`], counting each digit of a multi-digit number
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "12-3=9".parse().unwrap();
+    /// assert_eq!(4, equation.count_digits());
+    /// ```
+    pub fn count_digits(&self) -> usize {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.is_number())
+            .count()
+    }
+
+    /// Number of operators in the [`Equation`], including equal signs and negations
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "-1+5=4".parse().unwrap();
+    /// assert_eq!(3, equation.count_operators());
+    /// ```
+    pub fn count_operators(&self) -> usize {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.is_operator())
+            .count()
+    }
+
+    /// Operators of the [`Equation`] in order, including equal signs and negations
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation: Equation = "-1+5=4".parse().unwrap();
+    /// assert_eq!(
+    ///     vec![Symbol::Minus, Symbol::Plus, Symbol::Equal],
+    ///     equation.operators()
+    /// );
+    /// ```
+    pub fn operators(&self) -> Vec<Symbol> {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.is_operator())
+            .cloned()
+            .collect()
+    }
+
+    // How many symbols the equation consists of
+    pub(crate) fn get_number_of_symbols(&self) -> usize {
+        self.symbols.len()
+    }
+
     // get all equations that can be formed from matchstick movements
     pub(crate) fn move_n_matchsticks(&self, number_matchsticks: usize) -> Vec<Self> {
+        self.move_n_matchsticks_with_cache(
+            number_matchsticks,
+            &[],
+            &mut TransitionSequenceCache::default(),
+        )
+    }
+
+    // like [`Equation::move_n_matchsticks`] but the transition sequences are taken from the cache
+    // symbols at locked positions are left unchanged
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            level = "debug",
+            skip_all,
+            fields(number_matchsticks, number_symbols = self.symbols.len())
+        )
+    )]
+    pub(crate) fn move_n_matchsticks_with_cache(
+        &self,
+        number_matchsticks: usize,
+        locked_positions: &[usize],
+        transition_sequence_cache: &mut TransitionSequenceCache,
+    ) -> Vec<Self> {
         let number_symbols = self.symbols.len();
         let all_transition_sequences =
-            TransitionSequence::move_n(number_matchsticks, number_symbols);
+            transition_sequence_cache.move_n(number_matchsticks, number_symbols);
 
         // find all equations that can be formed using the original equation and the transition sequences
         let mut syntactically_correct_equations = Vec::new();
-        for transition_sequence in all_transition_sequences {
-            if let Ok(mut transitioned_equations) =
-                self.apply_transition_sequence(transition_sequence)
+        for transition_sequence in all_transition_sequences
+            .iter()
+            .filter(|transition_sequence| transition_sequence.leaves_unchanged(locked_positions))
+        {
+            if let Ok(transitioned_equations) = self.apply_transition_sequence(transition_sequence)
             {
-                syntactically_correct_equations.append(&mut transitioned_equations);
+                syntactically_correct_equations.extend(transitioned_equations);
             }
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            number_transition_sequences = all_transition_sequences.len(),
+            number_equations = syntactically_correct_equations.len(),
+            "formed equations by moving matchsticks"
+        );
+
         syntactically_correct_equations
     }
 
-    pub(crate) fn mathematically_validate(&self) -> Result<(), ()> {
+    // how many equations [`Equation::move_n_matchsticks_with_cache`] forms, without forming them
+    pub(crate) fn count_move_n_matchsticks(
+        &self,
+        number_matchsticks: usize,
+        locked_positions: &[usize],
+    ) -> usize {
+        let allow_grouping = self.allows_grouping();
+        let allow_decorative = self.allows_decorations();
+        TransitionSequence::move_n(number_matchsticks, self.symbols.len())
+            .iter()
+            .filter(|transition_sequence| transition_sequence.leaves_unchanged(locked_positions))
+            .map(|transition_sequence| {
+                // each combination of the resulting symbols forms one equation
+                self.symbols
+                    .iter()
+                    .zip(&transition_sequence.transitions)
+                    .map(|(symbol, transition)| {
+                        symbol
+                            .apply_transition_allowing(
+                                *transition,
+                                allow_grouping,
+                                allow_decorative,
+                            )
+                            .len()
+                    })
+                    .product::<usize>()
+            })
+            .sum()
+    }
+
+    // like [`Equation::move_n_matchsticks_with_cache`] but the equations are formed lazily,
+    // so searching them can stop at the first equation of interest
+    pub(crate) fn iter_move_n_matchsticks(
+        &self,
+        number_matchsticks: usize,
+        locked_positions: &[usize],
+    ) -> impl Iterator<Item = Self> {
+        TransitionSequence::move_n(number_matchsticks, self.symbols.len())
+            .into_iter()
+            .filter(|transition_sequence| transition_sequence.leaves_unchanged(locked_positions))
+            .filter_map(|transition_sequence| {
+                self.apply_transition_sequence(&transition_sequence).ok()
+            })
+            .flatten()
+    }
+
+    // get all distinct equations that can be formed from at most n matchstick movements
+    // including the equation itself formed by zero movements
+    // the transition sequences are taken from the cache
+    pub(crate) fn move_up_to_n_matchsticks_with_cache(
+        &self,
+        number_matchsticks: usize,
+        locked_positions: &[usize],
+        transition_sequence_cache: &mut TransitionSequenceCache,
+    ) -> Vec<Self> {
+        (0..=number_matchsticks)
+            .flat_map(|number_matchsticks| {
+                self.move_n_matchsticks_with_cache(
+                    number_matchsticks,
+                    locked_positions,
+                    transition_sequence_cache,
+                )
+            })
+            .unique()
+            .collect()
+    }
+
+    /// The fewest matchstick movements turning the [`Equation`] into a mathematically valid one,
+    /// trying up to `max` movements \
+    /// Returns `Some(0)` if the [`Equation`] is already valid
+    /// and `None` if no valid equation is formed within `max` movements
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// // "6 + 4 = 4" becomes "0 + 4 = 4" by moving the middle beam of the six
+    /// let equation: Equation = "6+4=4".parse().unwrap();
+    /// assert_eq!(Some(1), equation.min_moves_to_valid(3));
+    /// assert_eq!(None, equation.min_moves_to_valid(0));
+    /// ```
+    pub fn min_moves_to_valid(&self, max: usize) -> Option<usize> {
+        let mut transition_sequence_cache = TransitionSequenceCache::default();
+
+        (0..=max).find(|number_matchsticks| {
+            self.move_n_matchsticks_with_cache(
+                *number_matchsticks,
+                &[],
+                &mut transition_sequence_cache,
+            )
+            .iter()
+            .any(|equation| equation.mathematically_validate().is_ok())
+        })
+    }
+
+    /// All mathematically invalid equations turning into the [`Equation`]
+    /// by moving exactly `moves` matchsticks, i.e. the riddles it solves \
+    /// Moving matchsticks can be reversed, so these are formed by moving matchsticks
+    /// of the [`Equation`] itself. Each equation is listed once
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "0+4=4".parse().unwrap();
+    /// let predecessors = equation.predecessors(1);
+    ///
+    /// // "6 + 4 = 4" is solved by moving the middle beam of the six
+    /// assert!(predecessors.contains(&"6+4=4".parse().unwrap()));
+    /// assert!(predecessors.iter().all(|predecessor| predecessor.mathematically_validate().is_err()));
+    /// ```
+    pub fn predecessors(&self, moves: usize) -> Vec<Self> {
+        self.move_n_matchsticks(moves)
+            .into_iter()
+            .unique()
+            .filter(|equation| equation.mathematically_validate().is_err())
+            .collect()
+    }
+
+    /// All equations reachable by at most `k` segment changes over all positions,
+    /// not including the [`Equation`] itself \
+    /// Unlike moving matchsticks, segments may be removed and added independently,
+    /// so the number of matchsticks can change
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// // "1" becomes "7" by adding the top segment
+    /// let equation: Equation = "1".parse().unwrap();
+    /// assert_eq!(vec!["7".parse::<Equation>().unwrap()], equation.neighbors_within(1));
+    /// ```
+    pub fn neighbors_within(&self, k: usize) -> Vec<Self> {
+        // for each position all symbols within reach and the number of segment changes to reach them
+        let symbol_options = self
+            .symbols
+            .iter()
+            .map(|symbol| {
+                Symbol::get_all()
+                    .into_iter()
+                    .map(|target_symbol| {
+                        let transition = symbol.delta_to(&target_symbol);
+                        (target_symbol, transition.remove + transition.add)
+                    })
+                    .filter(|(_, number_changes)| *number_changes <= k)
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        let mut neighbors = Vec::new();
+        Self::collect_neighbors(&symbol_options, k, &mut Vec::new(), &mut neighbors);
+        neighbors.retain(|neighbor| neighbor != self);
+
+        neighbors
+    }
+
+    // picks a symbol for the next position as long as the remaining changes suffice
+    fn collect_neighbors(
+        symbol_options: &[Vec<(Symbol, usize)>],
+        remaining_changes: usize,
+        symbols: &mut Vec<Symbol>,
+        neighbors: &mut Vec<Self>,
+    ) {
+        let Some((options_for_position, options_for_next_positions)) = symbol_options.split_first()
+        else {
+            // all positions have a symbol
+            neighbors.push(Equation {
+                symbols: symbols.clone(),
+            });
+            return;
+        };
+
+        for (symbol, number_changes) in options_for_position {
+            if *number_changes <= remaining_changes {
+                symbols.push(symbol.clone());
+                Self::collect_neighbors(
+                    options_for_next_positions,
+                    remaining_changes - number_changes,
+                    symbols,
+                    neighbors,
+                );
+                symbols.pop();
+            }
+        }
+    }
+
+    /// Checks whether the [`Equation`] is a true mathematical statement
+    /// using the default [`ValidationOptions`]
+    pub fn mathematically_validate(&self) -> Result<(), ValidationError> {
+        self.validate_with_options(&ValidationOptions::default())
+    }
+
+    /// Checks whether the [`Equation`] is a true mathematical statement \
+    /// The [`ValidationOptions`] allow to reject equations that evaluate fine
+    /// but are unusual for matchstick puzzles
+    ///
+    /// There may be several equal signs, like in "1+1=2=4-2". Each expression between them
+    /// is evaluated on its own and compared to the first one. If any expression can't be evaluated,
+    /// its error is returned even if other expressions already differ
+    ///
+    /// An [`Equation`] without any symbols is a [`ValidationError::EmptyExpression`]
+    ///
+    /// With [`ValidationOptions::strict_alternation`] the order of numbers and operators
+    /// is checked before evaluating, so malformed equations are reported as
+    /// [`ValidationError::BrokenAlternation`]
+    ///
+    /// A [`Symbol::Minus`] is read as negation only at the very start of the equation
+    /// or directly after a [`Symbol::Equal`] or [`Symbol::ParenOpen`], e.g. "-3+5=2" or "5-(-8)=13". \
+    /// Anywhere else it must stand between two numbers, so "5+-3=2" or "--3=3"
+    /// are [`ValidationError::InvalidExpression`]s
+    ///
+    /// Decorative symbols like [`Symbol::Degree`] are units of the number they directly follow
+    /// and are left out when validating, so "45°+45°=90°" is valid. Anywhere else they are
+    /// [`ValidationError::InvalidExpression`]s, like in "°5=5"
+    ///
+    /// Every other operator needs an operand on both sides, so "2+=2" or "=2+3"
+    /// are [`ValidationError::DanglingOperator`]s
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::equation::validation::{ValidationError, ValidationOptions};
+    /// # use matchstick::symbol::Symbol;
+    /// // "05 + 3 = 8"
+    /// let equation = Equation::new_from_symbols(vec![
+    ///     Symbol::Zero,
+    ///     Symbol::Five,
+    ///     Symbol::Plus,
+    ///     Symbol::Three,
+    ///     Symbol::Equal,
+    ///     Symbol::EightVar1,
+    /// ]);
+    /// assert_eq!(Ok(()), equation.validate_with_options(&ValidationOptions::default()));
+    ///
+    /// let options = ValidationOptions {
+    ///     allow_leading_zeros: false,
+    ///     ..Default::default()
+    /// };
+    /// assert_eq!(
+    ///     Err(ValidationError::LeadingZero),
+    ///     equation.validate_with_options(&options)
+    /// );
+    /// ```
+    pub fn validate_with_options(
+        &self,
+        options: &ValidationOptions,
+    ) -> Result<(), ValidationError> {
+        self.validate_with_evaluator(options, &IntEvaluator)
+    }
+
+    /// Like [`Equation::validate_with_options`] but the expressions are evaluated
+    /// by the given [`Evaluator`] instead of the default [`IntEvaluator`]
+    pub fn validate_with_evaluator(
+        &self,
+        options: &ValidationOptions,
+        evaluator: &impl Evaluator,
+    ) -> Result<(), ValidationError> {
+        if self.symbols.is_empty() {
+            return Err(ValidationError::EmptyExpression);
+        }
+        if self.symbols.iter().any(Symbol::is_decorative) {
+            return match self.without_decorations() {
+                Some(equation) => equation.validate_with_evaluator(options, evaluator),
+                None => Err(ValidationError::InvalidExpression),
+            };
+        }
+        if !options.allow_leading_zeros && self.has_leading_zero() {
+            return Err(ValidationError::LeadingZero);
+        }
+        if options.require_single_equals
+            && self
+                .symbols
+                .iter()
+                .filter(|symbol| **symbol == Symbol::Equal)
+                .count()
+                > 1
+        {
+            return Err(ValidationError::MultipleEqualSigns);
+        }
+        if options.strict_alternation && !self.has_strict_alternation() {
+            return Err(ValidationError::BrokenAlternation);
+        }
+        if self.has_misplaced_negation() {
+            return Err(ValidationError::InvalidExpression);
+        }
+        if self.has_dangling_operator() {
+            return Err(ValidationError::DanglingOperator);
+        }
+
         let equation_string = self.to_plain_text();
 
-        let equation_expressions = equation_string.split("=").map(eval_int).collect::<Vec<_>>();
+        let equation_expressions = equation_string.split("=").collect::<Vec<_>>();
         if equation_expressions.len() < 2 {
-            return Err(());
+            return Err(ValidationError::MissingEqualSign);
         } // equation needs at least two expressions
-        let Some(Ok(value_first_expression)) = equation_expressions.first() else {
-            return Err(());
-        }; // there exits at least a first expression
-
-        for expression in &equation_expressions {
-            // check that all expressions have same value
-            match expression {
-                Ok(value) => {
-                    if value != value_first_expression {
-                        return Err(());
-                    }
+
+        // every expression is evaluated on its own before any values are compared
+        let values = equation_expressions
+            .into_iter()
+            .map(|expression| evaluator.evaluate(expression))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // check that all expressions have the same value as the first one
+        match values.iter().all(|value| *value == values[0]) {
+            true => Ok(()),
+            false => Err(ValidationError::UnequalExpressions),
+        }
+    }
+
+    /// Evaluates an expression without equal sign, like "12-3" \
+    /// Returns `None` if there is an equal sign or the expression can't be evaluated,
+    /// e.g. because of a misplaced negation like "5+-3"
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let expression: Equation = "12-3".parse().unwrap();
+    /// assert_eq!(Some(9), expression.evaluate_single());
+    ///
+    /// let equation: Equation = "12-3=9".parse().unwrap();
+    /// assert_eq!(None, equation.evaluate_single());
+    /// ```
+    pub fn evaluate_single(&self) -> Option<i64> {
+        if self.symbols.iter().any(Symbol::is_decorative) {
+            return self.without_decorations()?.evaluate_single();
+        }
+        if self.symbols.contains(&Symbol::Equal) || self.has_misplaced_negation() {
+            return None;
+        }
+
+        IntEvaluator.evaluate(&self.to_plain_text()).ok()
+    }
+
+    /// Whether the expression without equal sign evaluates to the target value
+    /// (s. [`Equation::evaluate_single`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let expression: Equation = "9+8".parse().unwrap();
+    /// assert!(expression.evaluates_to(17));
+    ///
+    /// let equation: Equation = "9+8=17".parse().unwrap();
+    /// assert!(!equation.evaluates_to(17));
+    /// ```
+    pub fn evaluates_to(&self, target: i64) -> bool {
+        self.evaluate_single() == Some(target)
+    }
+
+    /// Evaluates each expression between the equal signs on its own, from left to right \
+    /// There is one value per expression, so "1+1=2=4-2" gives three values and an expression
+    /// without equal sign a single one. The values are not compared, unlike when validating.
+    /// Returns `None` if any expression can't be evaluated (s. [`Equation::evaluate_single`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "1+1=2=4-2".parse().unwrap();
+    /// assert_eq!(Some(vec![2, 2, 2]), equation.evaluate());
+    ///
+    /// let equation: Equation = "7+3=4".parse().unwrap();
+    /// assert_eq!(Some(vec![10, 4]), equation.evaluate());
+    ///
+    /// let equation: Equation = "7+=4".parse().unwrap();
+    /// assert_eq!(None, equation.evaluate());
+    /// ```
+    pub fn evaluate(&self) -> Option<Vec<i64>> {
+        self.symbols
+            .split(|symbol| *symbol == Symbol::Equal)
+            .map(|expression| Equation::new_from_symbols(expression.to_vec()).evaluate_single())
+            .collect()
+    }
+
+    // the equation with its decorative symbols left out
+    // None if a decorative symbol doesn't directly follow a number it is the unit of
+    fn without_decorations(&self) -> Option<Self> {
+        let mut symbols = Vec::new();
+        for (index, symbol) in self.symbols.iter().enumerate() {
+            if !symbol.is_decorative() {
+                symbols.push(symbol.clone());
+            } else if index == 0 || !self.symbols[index - 1].is_number() {
+                return None;
+            }
+        }
+
+        Some(Equation { symbols })
+    }
+
+    // values of all numbers from left to right, e.g. [12, -3, 9] for "12+(-3)=9"
+    // a minus counts as part of the number if it is a negation
+    // None if a number is too large
+    pub(crate) fn numbers(&self) -> Option<Vec<i64>> {
+        self.symbols
+            .iter()
+            .enumerate()
+            .chunk_by(|(_, symbol)| symbol.is_number())
+            .into_iter()
+            .filter(|(is_number, _)| *is_number)
+            .map(|(_, digits)| {
+                let digits = digits.collect::<Vec<_>>();
+                let start = digits[0].0;
+                let digits_text = digits
+                    .iter()
+                    .map(|(_, digit)| digit.to_str())
+                    .collect::<String>();
+                let is_negated = start >= 1
+                    && self.symbols[start - 1] == Symbol::Minus
+                    && (start == 1
+                        || matches!(self.symbols[start - 2], Symbol::Equal | Symbol::ParenOpen));
+
+                let number = digits_text.parse::<i64>().ok()?;
+                Some(if is_negated { -number } else { number })
+            })
+            .collect()
+    }
+
+    // largest absolute difference between the value of the first side and any other side
+    // None without an equal sign or if a side can't be evaluated
+    pub(crate) fn side_difference(&self) -> Option<i64> {
+        if !self.symbols.contains(&Symbol::Equal) {
+            return None;
+        }
+
+        let values = self.evaluate()?;
+
+        values
+            .iter()
+            .map(|value| values[0].checked_sub(*value).map(i64::abs))
+            .try_fold(0, |max_difference, difference| {
+                Some(max_difference.max(difference?))
+            })
+    }
+
+    // numbers and operators alternate, starting and ending with a number
+    // only a negation may directly precede a number at the start of an expression, e.g. "-3+5=2"
+    // parentheses open where a number is expected and close after a number, e.g. "(-3+5)=2"
+    fn has_strict_alternation(&self) -> bool {
+        let mut expects_number = true;
+        let mut allows_negation = true;
+
+        for symbol in &self.symbols {
+            if *symbol == Symbol::ParenOpen && expects_number {
+                allows_negation = true; // parenthesized expression may start with a negation
+            } else if *symbol == Symbol::ParenClose && !expects_number {
+                // closed expression is used like a number
+            } else if symbol.is_number() {
+                // following digits continue the same number
+                expects_number = false;
+                allows_negation = false;
+            } else if !expects_number {
+                // operator after a number starts the next operand
+                expects_number = true;
+                allows_negation = *symbol == Symbol::Equal;
+            } else if *symbol == Symbol::Minus && allows_negation {
+                allows_negation = false;
+            } else {
+                return false; // operator where a number is expected
+            }
+        }
+
+        !expects_number
+    }
+
+    // a minus that neither starts an expression nor stands between two numbers, e.g. "5+-3"
+    // the evaluator would accept this as negation, but it is ambiguous with matchsticks
+    // parentheses start an expression when opened and act like a number when closed
+    fn has_misplaced_negation(&self) -> bool {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| **symbol == Symbol::Minus)
+            .any(|(index, _)| match index {
+                0 => false, // negation at the start of the equation
+                _ => {
+                    let previous_symbol = &self.symbols[index - 1];
+                    !previous_symbol.is_number()
+                        && !matches!(
+                            previous_symbol,
+                            Symbol::Equal | Symbol::ParenOpen | Symbol::ParenClose
+                        )
                 }
-                Err(_) => return Err(()),
+            })
+    }
+
+    // an operator without an operand on its left or right side, e.g. "2+=2" or "=2+3"
+    // a closed parenthesis ends an operand and an opened one starts it
+    // a negation needs no left operand and may follow an equal sign, e.g. "-2+3=1" or "1=-2+3"
+    fn has_dangling_operator(&self) -> bool {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| symbol.is_operator())
+            .any(|(index, symbol)| {
+                let previous_symbol = index.checked_sub(1).map(|index| &self.symbols[index]);
+                let next_symbol = self.symbols.get(index + 1);
+
+                let is_negation = *symbol == Symbol::Minus
+                    && matches!(
+                        previous_symbol,
+                        None | Some(Symbol::Equal | Symbol::ParenOpen)
+                    );
+                let has_left_operand = previous_symbol.is_some_and(|previous_symbol| {
+                    previous_symbol.is_number() || *previous_symbol == Symbol::ParenClose
+                });
+                let has_right_operand = next_symbol.is_some_and(|next_symbol| {
+                    next_symbol.is_number()
+                        || *next_symbol == Symbol::ParenOpen
+                        || (*symbol == Symbol::Equal && *next_symbol == Symbol::Minus)
+                });
+
+                !(is_negation || has_left_operand) || !has_right_operand
+            })
+    }
+
+    // a number with several digits starts with zero, e.g. "05"
+    fn has_leading_zero(&self) -> bool {
+        for (index, symbol) in self.symbols.iter().enumerate() {
+            let starts_number = index == 0 || !self.symbols[index - 1].is_number();
+            let followed_by_digit = self
+                .symbols
+                .get(index + 1)
+                .is_some_and(|next_symbol| next_symbol.is_number());
+            if *symbol == Symbol::Zero && starts_number && followed_by_digit {
+                return true;
             }
         }
 
-        Ok(())
+        false
     }
 
-    pub(crate) fn fulfills_abstract_equation(&self, abstract_equation: &EquationPattern) -> bool {
+    /// Whether each [`Symbol`] of the [`Equation`] fulfills the [`crate::symbol::SymbolFilter`]
+    /// at its position in the [`EquationPattern`] \
+    /// An [`Equation`] with more or fewer [`Symbol`]s than the [`EquationPattern`] has filters
+    /// never matches
+    /// ```
+    /// # use matchstick::equation::{Equation, EquationPattern};
+    /// let equation: Equation = "7-3=4".parse().unwrap();
+    ///
+    /// assert!(equation.matches_pattern(&"N-N=N".parse::<EquationPattern>().unwrap()));
+    /// assert!(!equation.matches_pattern(&"N+N=N".parse::<EquationPattern>().unwrap()));
+    /// assert!(!equation.matches_pattern(&"N-N".parse::<EquationPattern>().unwrap()));
+    /// ```
+    pub fn matches_pattern(&self, abstract_equation: &EquationPattern) -> bool {
+        if self.symbols.len() != abstract_equation.symbol_filters.len() {
+            return false;
+        }
         for (symbol, allowed_options) in self.symbols.iter().zip(&abstract_equation.symbol_filters)
         {
             if !allowed_options.get_corresponding_symbols().contains(symbol) {
@@ -99,6 +867,173 @@ impl Equation {
         true
     }
 
+    // number of digits that are shown in their alternative variant
+    pub(crate) fn count_alternative_variants(&self) -> usize {
+        self.symbols
+            .iter()
+            .filter(|symbol| symbol.is_alternative_variant())
+            .count()
+    }
+
+    /// Shows for each position how many segments need to change state
+    /// to reach the target [`Equation`] \
+    /// Returns `None` if the [`Equation`]s differ in length
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// # use matchstick::transition::{Transition, TransitionSequence};
+    /// let riddle_equation = Equation::new_from_symbols(vec![
+    ///     Symbol::Seven,
+    ///     Symbol::Minus,
+    ///     Symbol::Three,
+    ///     Symbol::Equal,
+    ///     Symbol::FourVar1,
+    /// ]);
+    /// let solution_equation = Equation::new_from_symbols(vec![
+    ///     Symbol::OneVar1,
+    ///     Symbol::Plus,
+    ///     Symbol::Three,
+    ///     Symbol::Equal,
+    ///     Symbol::FourVar1,
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     Some(TransitionSequence {
+    ///         transitions: vec![
+    ///             Transition { remove: 1, add: 0 },
+    ///             Transition { remove: 0, add: 1 },
+    ///             Transition::default(),
+    ///             Transition::default(),
+    ///             Transition::default(),
+    ///         ]
+    ///     }),
+    ///     riddle_equation.transition_to(&solution_equation)
+    /// );
+    /// assert_eq!(None, riddle_equation.transition_to(&Equation::new_from_symbols(vec![])));
+    /// ```
+    pub fn transition_to(&self, target: &Self) -> Option<TransitionSequence> {
+        if self.symbols.len() != target.symbols.len() {
+            return None;
+        }
+
+        let transitions = self
+            .symbols
+            .iter()
+            .zip(&target.symbols)
+            .map(|(symbol, target_symbol)| symbol.delta_to(target_symbol))
+            .collect();
+
+        Some(TransitionSequence { transitions })
+    }
+
+    // total number of segments that change state to reach the target equation
+    pub(crate) fn segment_delta_to(&self, target: &Self) -> usize {
+        self.symbols
+            .iter()
+            .zip(&target.symbols)
+            .map(|(symbol, target_symbol)| {
+                let transition = symbol.delta_to(target_symbol);
+                transition.remove + transition.add
+            })
+            .sum()
+    }
+
+    /// Whether the [`Equation`] reads the same forwards and backwards by value \
+    /// Variants of the same digit are considered equal (s. [`Symbol::same_value`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "2+2=2+2".parse().unwrap();
+    /// assert!(equation.is_value_palindrome());
+    ///
+    /// let equation: Equation = "12=12".parse().unwrap();
+    /// assert!(!equation.is_value_palindrome());
+    /// ```
+    pub fn is_value_palindrome(&self) -> bool {
+        self.symbols
+            .iter()
+            .zip(self.symbols.iter().rev())
+            .all(|(symbol, mirrored_symbol)| symbol.same_value(mirrored_symbol))
+    }
+
+    /// The [`Equation`] flipped from left to right \
+    /// The order of the symbols is reversed and each symbol is mirrored itself.
+    /// `None` if any symbol has no mirrored counterpart (s. [`Symbol::mirror_horizontal`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation: Equation = "2+1=0".parse().unwrap();
+    ///
+    /// assert_eq!(
+    ///     Some(Equation::new_from_symbols(vec![
+    ///         Symbol::Zero,
+    ///         Symbol::Equal,
+    ///         Symbol::OneVar2,
+    ///         Symbol::Plus,
+    ///         Symbol::Five,
+    ///     ])),
+    ///     equation.mirror_horizontal()
+    /// );
+    /// assert_eq!(None, "3=3".parse::<Equation>().unwrap().mirror_horizontal());
+    /// ```
+    pub fn mirror_horizontal(&self) -> Option<Self> {
+        let mirrored_symbols = self
+            .symbols
+            .iter()
+            .rev()
+            .map(Symbol::mirror_horizontal)
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Equation {
+            symbols: mirrored_symbols,
+        })
+    }
+
+    /// Inline LaTeX math of the [`Equation`] to place it in documents \
+    /// Operators between numbers are surrounded by spaces while a negation
+    /// directly precedes its number
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "12-15=-3".parse().unwrap();
+    /// assert_eq!("$12 - 15 = -3$", equation.to_latex());
+    /// ```
+    pub fn to_latex(&self) -> String {
+        let mut latex_equation = String::from("$");
+        for (index, symbol) in self.symbols.iter().enumerate() {
+            // operators are spaced unless they are a negation
+            let follows_operand = index > 0
+                && (self.symbols[index - 1].is_number()
+                    || self.symbols[index - 1] == Symbol::ParenClose);
+            if !symbol.is_operator() || !follows_operand {
+                latex_equation.push_str(symbol.to_str());
+            } else {
+                latex_equation.push_str(&format!(" {} ", symbol.to_str()));
+            }
+        }
+        latex_equation.push('$');
+
+        latex_equation
+    }
+
+    /// Stable text key of the [`Equation`] where each value is written once,
+    /// regardless of its variant, e.g. to deduplicate solutions in a `HashMap` \
+    /// Unlike `==` and `Hash` on [`Equation`], variants like [`Symbol::OneVar1`]
+    /// and [`Symbol::OneVar2`] give the same key
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation = Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1]);
+    /// let variant_equation =
+    ///     Equation::new_from_symbols(vec![Symbol::OneVar2, Symbol::Equal, Symbol::OneVar1]);
+    ///
+    /// assert_ne!(equation, variant_equation);
+    /// assert_eq!("1=1", equation.canonical_string());
+    /// assert_eq!(equation.canonical_string(), variant_equation.canonical_string());
+    /// ```
+    pub fn canonical_string(&self) -> String {
+        // all variants of a value share their text
+        self.to_plain_text()
+    }
+
     pub(crate) fn to_plain_text(&self) -> String {
         let mut string_equation = String::new();
         for symbol in &self.symbols {
@@ -107,40 +1042,108 @@ impl Equation {
         string_equation
     }
 
+    // text that is the same for equations only differing in the order of summed terms,
+    // e.g. "2+3=5" and "3+2=5" or "5-3=2" and "-3+5=2"
+    // each expression is a sum of signed terms, parenthesized terms are sorted on their own
+    // an equation that can't be read as sums keeps its plain text
+    pub(crate) fn commutative_key(&self) -> String {
+        let plain_text = self.to_plain_text();
+        let expression_keys = plain_text
+            .split('=')
+            .map(|expression| {
+                let mut characters = expression.chars().peekable();
+                let expression_key = sum_key(&mut characters)?;
+                characters.peek().is_none().then_some(expression_key)
+            })
+            .collect::<Option<Vec<_>>>();
+
+        match expression_keys {
+            Some(expression_keys) => expression_keys.join("="),
+            None => plain_text,
+        }
+    }
+
+    // Moving matchsticks only forms grouping symbols if the equation already contains some,
+    // so riddles without parentheses keep their solutions
+    pub(crate) fn allows_grouping(&self) -> bool {
+        self.symbols.iter().any(Symbol::is_grouping)
+    }
+
+    // Like [`Equation::allows_grouping`] but for decorative symbols like the degree sign
+    pub(crate) fn allows_decorations(&self) -> bool {
+        self.symbols.iter().any(Symbol::is_decorative)
+    }
+
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip_all))]
     fn apply_transition_sequence(
         &self,
-        transition_sequence: TransitionSequence,
-    ) -> Result<Vec<Self>, ()> {
+        transition_sequence: &TransitionSequence,
+    ) -> Result<impl Iterator<Item = Self> + use<>, ()> {
         if self.symbols.len() != transition_sequence.get_number_of_transitions() {
             return Err(());
         } // each symbol of equation needs corresponding transition element
 
         // apply each transition to respective symbol and collect potential, resulting symbols
+        let allow_grouping = self.allows_grouping();
+        let allow_decorative = self.allows_decorations();
         let mut transitioned_symbols = Vec::new();
-        for (symbol, transition) in self.symbols.iter().zip(transition_sequence.transitions) {
+        for (symbol, transition) in self.symbols.iter().zip(&transition_sequence.transitions) {
             // this transition applied to this symbol leads to valid, new symbol(s)
-            transitioned_symbols.push(symbol.apply_transition(transition))
+            let symbol_options =
+                symbol.apply_transition_allowing(*transition, allow_grouping, allow_decorative);
+
+            // without any valid symbol at this position no equation can be formed
+            // so the remaining symbols do not need to be transitioned
+            if symbol_options.is_empty() {
+                return Ok(Either::Left(iter::empty()));
+            }
+            transitioned_symbols.push(symbol_options)
         }
 
+        #[cfg(feature = "tracing")]
+        tracing::trace!(
+            number_equations = transitioned_symbols.iter().map(Vec::len).product::<usize>(),
+            "applied transition sequence"
+        );
+
         // there are resulting symbols for the first, second, and so on original symbol
         // form each possible equation by applying cartesian product
         // Eg. if original equation consisted of symbols "(A, G, E)" and those symbols transitioned to ((F, T), (H), (N, R, W))
         // all combinations of transitioned equations would be: (F, H, N) and (F, H, R) and (F, H, W) and (T, H, N) and so on
-        let all_symbol_combinations = transitioned_symbols
+        // The combinations are streamed straight into Equations
+        // instead of collecting all symbol combinations first
+        let all_transitioned_equations = transitioned_symbols
             .into_iter()
             .map(|all_symbol_options| all_symbol_options.into_iter())
             .multi_cartesian_product()
-            .collect::<Vec<_>>();
-
-        // put vector of symbols into Equation
-        let all_transitioned_equations = all_symbol_combinations
-            .into_iter()
             .map(|symbols_of_one_equation| Equation {
                 symbols: symbols_of_one_equation,
-            })
-            .collect();
+            });
 
-        Ok(all_transitioned_equations)
+        Ok(Either::Right(all_transitioned_equations))
+    }
+
+    /// The [`Equation`] turned upside down by 180 degrees \
+    /// The order of the symbols is reversed and each symbol is turned itself.
+    /// `None` if any symbol can't be turned (s. [`Symbol::rotate_180`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "6+2=8".parse().unwrap();
+    ///
+    /// assert_eq!(Some("8=2+9".parse().unwrap()), equation.rotate_180());
+    /// assert_eq!(None, "7=7".parse::<Equation>().unwrap().rotate_180());
+    /// ```
+    pub fn rotate_180(&self) -> Option<Self> {
+        let rotated_symbols = self
+            .symbols
+            .iter()
+            .rev()
+            .map(Symbol::rotate_180)
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(Equation {
+            symbols: rotated_symbols,
+        })
     }
 
     /// Drawing of [`Equation`] to visualize matchstick placement
@@ -163,11 +1166,141 @@ impl Equation {
     #[doc = "|___       ___|      ___|\");"]
     /// ```
     pub fn draw(&self) -> String {
-        let mut segment_display_lines = vec![String::new(); 5];
+        draw_segment_displays(&self.to_segment_displays())
+    }
+
+    /// The [`SegmentDisplay`] of each [`Symbol`] of the [`Equation`]
+    /// (s. [`Equation::from_segment_displays`])
+    pub fn to_segment_displays(&self) -> Vec<SegmentDisplay> {
+        self.symbols
+            .iter()
+            .map(Symbol::to_segment_display)
+            .collect()
+    }
+
+    /// Reads an [`Equation`] from [`SegmentDisplay`]s, e.g. recognized in an image \
+    /// Each [`SegmentDisplay`] must light the segments of a [`Symbol`] exactly,
+    /// otherwise the indices of all unmatched [`SegmentDisplay`]s are returned
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::segment_display::Segment;
+    /// let equation: Equation = "7-3=4".parse().unwrap();
+    /// let mut segment_displays = equation.to_segment_displays();
+    /// assert_eq!(Ok(equation), Equation::from_segment_displays(segment_displays.clone()));
+    ///
+    /// // a minus with a lit top segment is no symbol
+    /// segment_displays[1].set(Segment::Top, true);
+    /// assert_eq!(Err(vec![1]), Equation::from_segment_displays(segment_displays));
+    /// ```
+    pub fn from_segment_displays(displays: Vec<SegmentDisplay>) -> Result<Self, Vec<usize>> {
+        let symbols = displays
+            .iter()
+            .map(Symbol::from_segment_display)
+            .collect::<Vec<_>>();
+        let unmatched_indices = symbols
+            .iter()
+            .positions(Option::is_none)
+            .collect::<Vec<_>>();
+
+        match unmatched_indices.is_empty() {
+            true => Ok(Equation::new_from_symbols(
+                symbols.into_iter().flatten().collect(),
+            )),
+            false => Err(unmatched_indices),
+        }
+    }
+
+    /// Drawing of [`Equation`] like [`Equation::draw`] with a header line
+    /// numbering each symbol above its column, e.g. to find positions for debugging
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "7+1".parse().unwrap();
+    /// let drawing = equation.draw_indexed();
+    ///
+    /// let (header, matchsticks) = drawing.split_once('\n').unwrap();
+    /// assert_eq!("  0    1    2  ", header);
+    /// assert_eq!(equation.draw(), matchsticks);
+    /// ```
+    pub fn draw_indexed(&self) -> String {
+        // each symbol is drawn five characters wide
+        let header = (0..self.symbols.len())
+            .map(|index| format!("{index:^5}"))
+            .collect::<String>();
+
+        format!("{header}\n{}", self.draw())
+    }
+
+    /// Drawings to animate moving the matchsticks from this [`Equation`] to the target \
+    /// The first frame shows this equation, the second one without the removed matchsticks
+    /// and the last one the target equation with the matchsticks added again.
+    /// If the equations consist of a different number of symbols, there are no frames
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "3=8".parse().unwrap();
+    /// let frames = equation.interpolate_frames(&"9=9".parse().unwrap());
+    ///
+    /// assert_eq!(3, frames.len());
+    /// assert_eq!(equation.draw(), frames[0]);
+    /// // the lower left matchstick of the eight is removed
+    /// assert_eq!(frames[1],
+    #[doc = "\" ___       ___ "]
+    #[doc = "    | _ _ |   |"]
+    #[doc = " _ _| _ _ |_ _|"]
+    #[doc = "    |         |"]
+    #[doc = " ___|      ___|\");"]
+    /// ```
+    pub fn interpolate_frames(&self, target: &Self) -> Vec<String> {
+        if self.symbols.len() != target.symbols.len() {
+            return Vec::new();
+        }
+
+        // only segments lit in both equations are shown while the matchsticks are moved
+        let remaining_segment_displays = self
+            .symbols
+            .iter()
+            .zip(&target.symbols)
+            .map(|(symbol, target_symbol)| {
+                let mut segment_display = symbol.to_segment_display();
+                let (removed_segments, _) =
+                    segment_display.changed_segments(&target_symbol.to_segment_display());
+                for segment in removed_segments {
+                    segment_display.set(segment, false);
+                }
+                segment_display
+            })
+            .collect::<Vec<_>>();
+
+        vec![
+            self.draw(),
+            draw_segment_displays(&remaining_segment_displays),
+            target.draw(),
+        ]
+    }
+
+    /// Like [`Equation::draw`] but each symbol is enlarged by the given scale
+    /// (s. [`crate::segment_display::SegmentDisplay::draw_scaled`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "7+1".parse().unwrap();
+    ///
+    /// assert_eq!(equation.draw_scaled(2),
+    #[doc = "\" _____               "]
+    #[doc = "      |             |"]
+    #[doc = "      |             |"]
+    #[doc = "      |   |         |"]
+    #[doc = "      | __|__       |"]
+    #[doc = "      |   |         |"]
+    #[doc = "      |   |         |"]
+    #[doc = "      |             |"]
+    #[doc = "      |             |\");"]
+    /// ```
+    pub fn draw_scaled(&self, scale: usize) -> String {
+        let mut segment_display_lines = vec![String::new(); 4 * scale.max(1) + 1];
 
         for symbol in &self.symbols {
             symbol
-                .draw()
+                .to_segment_display()
+                .draw_scaled(scale)
                 .split('\n')
                 .enumerate()
                 .for_each(|(index, line)| {
@@ -181,46 +1314,78 @@ impl Equation {
     }
 }
 
-/// Sequence of [`SymbolFilter`]s generally describing an equation
-#[derive(Clone, Debug, PartialEq)]
-pub struct EquationPattern {
-    symbol_filters: Vec<SymbolFilter>,
-}
+impl IntoIterator for Equation {
+    type Item = Symbol;
+    type IntoIter = std::vec::IntoIter<Symbol>;
 
-impl EquationPattern {
-    /// Create new [`EquationPattern`]
-    pub fn new_from_symbol_filters(symbol_filters: Vec<SymbolFilter>) -> Self {
-        EquationPattern { symbol_filters }
+    fn into_iter(self) -> Self::IntoIter {
+        self.symbols.into_iter()
     }
+}
 
-    pub(crate) fn derive_concrete_equations(&self) -> Vec<Equation> {
-        let mut symbols_for_positions = Vec::new();
+impl<'a> IntoIterator for &'a Equation {
+    type Item = &'a Symbol;
+    type IntoIter = std::slice::Iter<'a, Symbol>;
 
-        // go through each symbol position of the abstract equation
-        // and retrieve all allowed symbols for this position
-        for filter in &self.symbol_filters {
-            let symbols_for_position = filter.get_corresponding_symbols();
-            symbols_for_positions.push(symbols_for_position);
+    fn into_iter(self) -> Self::IntoIter {
+        self.symbols.iter()
+    }
+}
+
+// terms of a sum with their signs in sorted order, e.g. "+2+3" for "3+2"
+// stops before a closing parenthesis or anything else that can't continue the sum
+fn sum_key(characters: &mut Peekable<Chars>) -> Option<String> {
+    let mut terms = Vec::new();
+    let mut sign = characters.next_if_eq(&'-').unwrap_or('+');
+    loop {
+        terms.push(format!("{sign}{}", operand_key(characters)?));
+        match characters.next_if(|character| matches!(character, '+' | '-')) {
+            Some(next_sign) => sign = next_sign,
+            None => break,
         }
+    }
+    terms.sort();
 
-        // create all symbol combinations for the equation
-        let all_symbol_combinations = symbols_for_positions
-            .into_iter()
-            .map(|all_symbol_options| all_symbol_options.into_iter())
-            .multi_cartesian_product()
-            .collect::<Vec<_>>();
+    Some(terms.concat())
+}
 
-        // put vector of symbols into Equation
-        all_symbol_combinations
-            .into_iter()
-            .map(Equation::new_from_symbols)
-            .collect()
+// a number or a parenthesized sum
+fn operand_key(characters: &mut Peekable<Chars>) -> Option<String> {
+    if characters.next_if_eq(&'(').is_some() {
+        let inner_key = sum_key(characters)?;
+        characters.next_if_eq(&')')?;
+        return Some(format!("({inner_key})"));
     }
+
+    let number = iter::from_fn(|| characters.next_if(char::is_ascii_digit)).collect::<String>();
+    (!number.is_empty()).then_some(number)
+}
+
+// draws the segment displays next to each other
+fn draw_segment_displays(segment_displays: &[SegmentDisplay]) -> String {
+    let mut segment_display_lines = vec![String::new(); 5];
+
+    for segment_display in segment_displays {
+        segment_display
+            .draw()
+            .split('\n')
+            .enumerate()
+            .for_each(|(index, line)| {
+                if let Some(string) = segment_display_lines.get_mut(index) {
+                    string.push_str(line)
+                }
+            });
+    }
+
+    segment_display_lines.join("\n")
 }
 
 #[cfg(test)]
 mod tests {
+    use std::collections::{HashMap, HashSet};
+
     use super::*;
+    use crate::segment_display::Segment;
     use crate::transition::Transition;
 
     #[test]
@@ -244,24 +1409,325 @@ mod tests {
             ],
         };
 
-        let expected_equation = Equation {
-            symbols: vec![
-                Symbol::Seven,
-                Symbol::Minus,
-                Symbol::Seven,
-                Symbol::Equal,
-                Symbol::Zero,
-            ],
+        let expected_equation = Equation {
+            symbols: vec![
+                Symbol::Seven,
+                Symbol::Minus,
+                Symbol::Seven,
+                Symbol::Equal,
+                Symbol::Zero,
+            ],
+        };
+
+        let resulting_equations = equation
+            .apply_transition_sequence(&transition_sequence)
+            .unwrap()
+            .collect::<Vec<_>>();
+        if resulting_equations.len() != 1 {
+            panic!("Should only result in one transitioned equation.")
+        }
+
+        assert_eq!(expected_equation, resulting_equations[0])
+    }
+
+    #[test]
+    fn same_equations_from_physically_possible_sequences() {
+        for plain_text in ["1+1=2", "(0+7)=4", "9-5=4"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            let transition_sequences = TransitionSequence::move_n(2, equation.symbols.len());
+            let form_equations = |transition_sequences: Vec<&TransitionSequence>| {
+                transition_sequences
+                    .into_iter()
+                    .flat_map(|transition_sequence| {
+                        equation
+                            .apply_transition_sequence(transition_sequence)
+                            .unwrap()
+                    })
+                    .collect::<Vec<_>>()
+            };
+
+            let possible_sequences = transition_sequences
+                .iter()
+                .filter(|transition_sequence| transition_sequence.is_physically_possible(&equation))
+                .collect::<Vec<_>>();
+            assert!(possible_sequences.len() < transition_sequences.len());
+            assert_eq!(
+                form_equations(transition_sequences.iter().collect()),
+                form_equations(possible_sequences),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn value_palindrome_with_variants() {
+        let equation = Equation {
+            symbols: vec![
+                Symbol::OneVar1,
+                Symbol::FourVar2,
+                Symbol::Equal,
+                Symbol::FourVar1,
+                Symbol::OneVar2,
+            ],
+        };
+        assert!(equation.is_value_palindrome());
+        assert!(Equation::new_from_symbols(vec![]).is_value_palindrome());
+        assert!(!Equation::new_from_symbols(vec![Symbol::Two, Symbol::Five]).is_value_palindrome());
+    }
+
+    #[test]
+    fn latex_with_leading_negation() {
+        let equation: Equation = "-2+5=3".parse().unwrap();
+        assert_eq!("$-2 + 5 = 3$", equation.to_latex());
+    }
+
+    #[test]
+    fn neighbors_within_segment_changes() {
+        let equation: Equation = "3=8".parse().unwrap();
+
+        let neighbors = equation.neighbors_within(2);
+
+        assert!(!neighbors.is_empty());
+        assert!(!neighbors.contains(&equation));
+        for neighbor in &neighbors {
+            let number_changes = equation.segment_delta_to(neighbor);
+            assert!((1..=2).contains(&number_changes));
+        }
+        // "9 = 9" needs one addition and one removal, "9 = 8" only the addition
+        assert!(neighbors.contains(&"9=9".parse().unwrap()));
+        assert!(neighbors.contains(&"9=8".parse().unwrap()));
+    }
+
+    #[test]
+    fn sort_equations() {
+        let mut equations = vec![
+            Equation {
+                symbols: vec![Symbol::Two, Symbol::Equal, Symbol::Two],
+            },
+            Equation {
+                symbols: vec![Symbol::Plus, Symbol::Two, Symbol::Equal, Symbol::Two],
+            },
+            Equation {
+                symbols: vec![Symbol::Two, Symbol::Equal],
+            },
+        ];
+        equations.sort();
+
+        let expected_equations = vec![
+            Equation {
+                symbols: vec![Symbol::Plus, Symbol::Two, Symbol::Equal, Symbol::Two],
+            },
+            Equation {
+                symbols: vec![Symbol::Two, Symbol::Equal],
+            },
+            Equation {
+                symbols: vec![Symbol::Two, Symbol::Equal, Symbol::Two],
+            },
+        ];
+
+        assert_eq!(expected_equations, equations);
+    }
+
+    #[test]
+    fn deduplicate_equations_in_hash_set() {
+        let equation = Equation {
+            symbols: vec![Symbol::Two, Symbol::Equal, Symbol::Two],
+        };
+        let variant_equation = Equation {
+            symbols: vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1],
+        };
+        let other_variant_equation = Equation {
+            symbols: vec![Symbol::OneVar2, Symbol::Equal, Symbol::OneVar1],
+        };
+
+        let unique_equations = HashSet::from([
+            equation.clone(),
+            variant_equation.clone(),
+            equation,
+            other_variant_equation,
+            variant_equation,
+        ]);
+
+        assert_eq!(3, unique_equations.len());
+    }
+
+    #[test]
+    fn deduplicate_variants_by_canonical_string() {
+        let equations = [
+            Equation {
+                symbols: vec![Symbol::FourVar1, Symbol::Equal, Symbol::EightVar1],
+            },
+            Equation {
+                symbols: vec![Symbol::FourVar2, Symbol::Equal, Symbol::EightVar2],
+            },
+            Equation {
+                symbols: vec![Symbol::FourVar1, Symbol::Equal, Symbol::EightVar2],
+            },
+            Equation {
+                symbols: vec![Symbol::Nine, Symbol::Equal, Symbol::EightVar1],
+            },
+        ];
+
+        let unique_equations: HashMap<String, &Equation> = equations
+            .iter()
+            .map(|equation| (equation.canonical_string(), equation))
+            .collect();
+
+        assert_eq!(2, unique_equations.len());
+        assert!(unique_equations.contains_key("4=8"));
+        assert!(unique_equations.contains_key("9=8"));
+    }
+
+    #[test]
+    fn same_commutative_key_for_reordered_terms() {
+        let commutative_key =
+            |plain_text: &str| plain_text.parse::<Equation>().unwrap().commutative_key();
+
+        assert_eq!(commutative_key("2+3=5"), commutative_key("3+2=5"));
+        assert_eq!(commutative_key("5-3=2"), commutative_key("-3+5=2"));
+        assert_eq!(commutative_key("9-(3+2)=4"), commutative_key("-(2+3)+9=4"));
+        assert_ne!(commutative_key("5-3=2"), commutative_key("3-5=2"));
+        assert_ne!(commutative_key("2+3=5"), commutative_key("5=2+3"));
+        // malformed equations keep their text
+        assert_eq!("2+=(5", commutative_key("2+=(5"));
+    }
+
+    #[test]
+    fn read_equation_from_segment_displays() {
+        let equation = Equation {
+            symbols: vec![
+                Symbol::OneVar2,
+                Symbol::ParenOpen,
+                Symbol::FourVar2,
+                Symbol::ParenClose,
+                Symbol::Equal,
+                Symbol::EightVar2,
+            ],
+        };
+        assert_eq!(
+            Ok(equation.clone()),
+            Equation::from_segment_displays(equation.to_segment_displays())
+        );
+
+        let mut unlit_segment_display = Symbol::EightVar1.to_segment_display();
+        for segment in Segment::get_all() {
+            unlit_segment_display.set(segment, false);
+        }
+        let unlit_segment_displays = vec![unlit_segment_display; 2];
+        assert_eq!(
+            Err(vec![0, 1]),
+            Equation::from_segment_displays(unlit_segment_displays)
+        );
+    }
+
+    #[test]
+    fn collect_numbers_with_negations() {
+        let equation: Equation = "-12+(-3)-4=05".parse().unwrap();
+        assert_eq!(Some(vec![-12, -3, 4, 5]), equation.numbers());
+
+        let equation: Equation = "99999999999999999999=1".parse().unwrap();
+        assert_eq!(None, equation.numbers());
+    }
+
+    #[test]
+    fn predecessors_are_solved_by_equation() {
+        use crate::puzzle::riddle::Riddle;
+
+        let equation: Equation = "9-5=4".parse().unwrap();
+        let predecessors = equation.predecessors(2);
+
+        assert!(!predecessors.is_empty());
+        for predecessor in predecessors {
+            assert!(Riddle::new(predecessor, 2).accepts(&equation));
+        }
+    }
+
+    #[test]
+    fn leave_out_decorative_units() {
+        let equation: Equation = "45°+45°=90°".parse().unwrap();
+        assert_eq!(Ok(()), equation.mathematically_validate());
+        assert_eq!(Some(vec![90, 90]), equation.evaluate());
+
+        let equation: Equation = "45°+45°=80°".parse().unwrap();
+        assert_eq!(
+            Err(ValidationError::UnequalExpressions),
+            equation.mathematically_validate()
+        );
+
+        for plain_text in ["°5=5", "5=5+°", "5°°=5", "°"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Err(ValidationError::InvalidExpression),
+                equation.mathematically_validate(),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn no_pattern_match_with_different_length() {
+        let equation_pattern: EquationPattern = "N+N=N".parse().unwrap();
+
+        let equation: Equation = "1+3=4".parse().unwrap();
+        assert!(equation.matches_pattern(&equation_pattern));
+        // longer with a matching prefix
+        let equation: Equation = "1+3=45".parse().unwrap();
+        assert!(!equation.matches_pattern(&equation_pattern));
+        // shorter with all symbols matching
+        let equation: Equation = "1+3=".parse().unwrap();
+        assert!(!equation.matches_pattern(&equation_pattern));
+    }
+
+    #[test]
+    fn check_order_of_symbols_when_constructing() {
+        for plain_text in ["1+3=4", "2=-3+5", "(2+3)-1=4", "90°=45°+45°", "7"] {
+            let symbols = plain_text.parse::<Equation>().unwrap().into_symbols();
+            assert!(Equation::try_new(symbols).is_ok(), "{plain_text}");
+        }
+        for plain_text in ["=2+3", "2+3=", "5+-3=2", "2++3=5", "°5=5"] {
+            let symbols = plain_text.parse::<Equation>().unwrap().into_symbols();
+            assert_eq!(
+                Err(EquationError::BrokenAlternation),
+                Equation::try_new(symbols),
+                "{plain_text}"
+            );
+        }
+        assert_eq!(Err(EquationError::Empty), Equation::try_new(vec![]));
+    }
+
+    #[test]
+    fn form_equations_of_fixed_length() {
+        assert_eq!(Symbol::get_all().len(), Equation::all_of_length(1).count());
+        assert_eq!(
+            vec![Equation::new_from_symbols(vec![Symbol::Equal])],
+            Equation::all_of_length_with_one_equal_sign(1).collect::<Vec<_>>()
+        );
+        assert!(
+            Equation::all_of_length_with_one_equal_sign(2)
+                .any(|equation| equation.to_plain_text() == "1=")
+        );
+    }
+
+    #[test]
+    fn iterate_over_symbols() {
+        let equation = Equation {
+            symbols: vec![Symbol::Seven, Symbol::Equal, Symbol::Seven],
         };
 
-        let resulting_equations = equation
-            .apply_transition_sequence(transition_sequence)
-            .unwrap();
-        if resulting_equations.len() != 1 {
-            panic!("Should only result in one transitioned equation.")
-        }
+        let borrowed_symbols = (&equation).into_iter().cloned().collect::<Vec<_>>();
+        assert_eq!(equation.get_symbols(), borrowed_symbols);
 
-        assert_eq!(expected_equation, resulting_equations[0])
+        let owned_symbols = equation
+            .into_iter()
+            .map(|symbol| match symbol {
+                Symbol::Seven => Symbol::OneVar1,
+                other_symbol => other_symbol,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1],
+            owned_symbols
+        );
     }
 
     #[test]
@@ -307,6 +1773,24 @@ mod tests {
         assert_eq!(Ok(()), equation.mathematically_validate())
     }
 
+    #[test]
+    fn valid_equation_leading_negation() {
+        let equation: Equation = "-3+5=2".parse().unwrap();
+        assert_eq!(Ok(()), equation.mathematically_validate())
+    }
+
+    #[test]
+    fn reject_negation_after_operator() {
+        for plain_text in ["5+-3=2", "5--3=8", "--3=3", "3=--3"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Err(ValidationError::InvalidExpression),
+                equation.mathematically_validate(),
+                "{plain_text}"
+            );
+        }
+    }
+
     #[test]
     fn invalid_equation() {
         let equation = Equation {
@@ -318,7 +1802,10 @@ mod tests {
                 Symbol::Two,
             ],
         };
-        assert_eq!(Err(()), equation.mathematically_validate())
+        assert_eq!(
+            Err(ValidationError::UnequalExpressions),
+            equation.mathematically_validate()
+        )
     }
 
     #[test]
@@ -332,7 +1819,10 @@ mod tests {
                 Symbol::Seven,
             ],
         };
-        assert_eq!(Err(()), equation.mathematically_validate())
+        assert_eq!(
+            Err(ValidationError::MissingEqualSign),
+            equation.mathematically_validate()
+        )
     }
 
     #[test]
@@ -347,7 +1837,77 @@ mod tests {
                 Symbol::Two,
             ],
         };
-        assert_eq!(Err(()), equation.mathematically_validate())
+        assert_eq!(
+            Err(ValidationError::DanglingOperator),
+            equation.mathematically_validate()
+        )
+    }
+
+    #[test]
+    fn reject_dangling_operators() {
+        for equation in ["2+=2", "=2+3", "2+3=", "-=1", "(2-)3=1"] {
+            assert_eq!(
+                Err(ValidationError::DanglingOperator),
+                equation
+                    .parse::<Equation>()
+                    .unwrap()
+                    .mathematically_validate(),
+                "{equation}"
+            );
+        }
+        for equation in ["-2+3=1", "1=-2+3", "(-2)+3=1", "5-(-8)=13"] {
+            assert_eq!(
+                Ok(()),
+                equation
+                    .parse::<Equation>()
+                    .unwrap()
+                    .mathematically_validate(),
+                "{equation}"
+            );
+        }
+    }
+
+    #[test]
+    fn reject_leading_zero() {
+        let equation = Equation {
+            symbols: vec![
+                Symbol::Zero,
+                Symbol::Five,
+                Symbol::Plus,
+                Symbol::Three,
+                Symbol::Equal,
+                Symbol::EightVar1,
+            ],
+        };
+        let options = ValidationOptions {
+            allow_leading_zeros: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(ValidationError::LeadingZero),
+            equation.validate_with_options(&options)
+        );
+        assert_eq!(Ok(()), equation.mathematically_validate())
+    }
+
+    #[test]
+    fn accept_standalone_zero() {
+        let equation = Equation {
+            symbols: vec![
+                Symbol::OneVar1,
+                Symbol::Zero,
+                Symbol::Minus,
+                Symbol::Zero,
+                Symbol::Equal,
+                Symbol::OneVar2,
+                Symbol::Zero,
+            ],
+        };
+        let options = ValidationOptions {
+            allow_leading_zeros: false,
+            ..Default::default()
+        };
+        assert_eq!(Ok(()), equation.validate_with_options(&options))
     }
 
     #[test]
@@ -364,6 +1924,205 @@ mod tests {
         assert_eq!(Ok(()), equation.mathematically_validate())
     }
 
+    #[test]
+    fn reject_overflowing_operand() {
+        // "1111111111111111111111111111111111111111 + 1 = 2"
+        let mut symbols = vec![Symbol::OneVar1; 40];
+        symbols.extend([Symbol::Plus, Symbol::OneVar1, Symbol::Equal, Symbol::Two]);
+        let equation = Equation { symbols };
+
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            equation.mathematically_validate()
+        );
+        assert_eq!(None, equation.evaluate_single());
+    }
+
+    #[test]
+    fn reject_overflowing_result() {
+        // "9999999999999999999 = 9999999999999999999" doesn't fit into i64
+        let mut symbols = vec![Symbol::Nine; 19];
+        symbols.push(Symbol::Equal);
+        symbols.extend(vec![Symbol::Nine; 19]);
+        let equation = Equation { symbols };
+
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            equation.mathematically_validate()
+        );
+    }
+
+    #[test]
+    fn valid_equation_with_three_expressions() {
+        let equation: Equation = "1+1=2=4-2".parse().unwrap();
+        assert_eq!(Ok(()), equation.mathematically_validate());
+
+        let equation: Equation = "1+1=2=5-2".parse().unwrap();
+        assert_eq!(
+            Err(ValidationError::UnequalExpressions),
+            equation.mathematically_validate()
+        );
+    }
+
+    #[test]
+    fn reject_unevaluable_expression_after_unequal_expressions() {
+        let equation: Equation = "1+1=3=(4".parse().unwrap();
+        assert_eq!(
+            Err(ValidationError::InvalidExpression),
+            equation.mathematically_validate()
+        );
+    }
+
+    #[test]
+    fn reject_two_individual_equal_signs() {
+        let equation = Equation {
+            symbols: vec![
+                Symbol::Two,
+                Symbol::Equal,
+                Symbol::Two,
+                Symbol::Equal,
+                Symbol::Two,
+            ],
+        };
+        let options = ValidationOptions {
+            require_single_equals: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            Err(ValidationError::MultipleEqualSigns),
+            equation.validate_with_options(&options)
+        )
+    }
+
+    #[test]
+    fn reject_broken_alternation() {
+        let options = ValidationOptions {
+            strict_alternation: true,
+            ..Default::default()
+        };
+        for plain_text in ["5+-3=2", "--3=3", "3=3-", "=3=3", "3==3"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Err(ValidationError::BrokenAlternation),
+                equation.validate_with_options(&options),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn accept_strict_alternation() {
+        let options = ValidationOptions {
+            strict_alternation: true,
+            ..Default::default()
+        };
+        for plain_text in ["-3+5=2", "5-8=-3", "12-3=9", "2=2=2"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Ok(()),
+                equation.validate_with_options(&options),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn count_digits_and_operators() {
+        let equation: Equation = "(12+3)-4=11".parse().unwrap();
+        assert_eq!(6, equation.count_digits());
+        assert_eq!(3, equation.count_operators());
+        assert_eq!(
+            equation.get_number_of_symbols() - 2, // parentheses are neither digits nor operators
+            equation.count_digits() + equation.count_operators()
+        );
+    }
+
+    #[test]
+    fn list_operators_like_counted() {
+        for plain_text in ["12-3=9", "(1+2)-3=0", "7", "°"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            let operators = equation.operators();
+            assert_eq!(equation.count_operators(), operators.len(), "{plain_text}");
+            assert!(operators.iter().all(Symbol::is_operator));
+        }
+    }
+
+    #[test]
+    fn align_indices_with_symbols() {
+        let equation: Equation = "10+2=12".parse().unwrap();
+        let drawing = equation.draw_indexed();
+        let lines = drawing.lines().collect::<Vec<_>>();
+
+        assert_eq!(6, lines.len());
+        assert!(lines.iter().all(|line| line.len() == 7 * 5));
+        assert_eq!("  6  ", &lines[0][30..]);
+    }
+
+    #[test]
+    fn empty_equation() {
+        let equation = Equation::new_from_symbols(vec![]);
+
+        assert_eq!(
+            Err(ValidationError::EmptyExpression),
+            equation.mathematically_validate()
+        );
+        assert_eq!(None, equation.evaluate_single());
+        assert_eq!(0, equation.matchstick_count());
+        assert_eq!("\n\n\n\n", equation.draw());
+    }
+
+    #[test]
+    fn no_moves_for_valid_equation() {
+        let equation: Equation = "1+1=2".parse().unwrap();
+        assert_eq!(Some(0), equation.min_moves_to_valid(2));
+    }
+
+    #[test]
+    fn parentheses_change_result() {
+        let equation: Equation = "9-(3+2)=4".parse().unwrap();
+        assert_eq!(Ok(()), equation.mathematically_validate());
+
+        let equation: Equation = "9-3+2=4".parse().unwrap();
+        assert_eq!(
+            Err(ValidationError::UnequalExpressions),
+            equation.mathematically_validate()
+        );
+    }
+
+    #[test]
+    fn negation_and_subtraction_next_to_parentheses() {
+        let options = ValidationOptions {
+            strict_alternation: true,
+            ..Default::default()
+        };
+        for plain_text in ["5-(-8)=13", "(2+3)-1=4", "-(3-5)=2"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Ok(()),
+                equation.validate_with_options(&options),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn reject_malformed_parentheses() {
+        for plain_text in ["2(3)=6", "(2+3=5", "()=0"] {
+            let equation: Equation = plain_text.parse().unwrap();
+            assert_eq!(
+                Err(ValidationError::InvalidExpression),
+                equation.mathematically_validate(),
+                "{plain_text}"
+            );
+        }
+    }
+
+    #[test]
+    fn latex_with_parentheses() {
+        let equation: Equation = "9-(3+2)=4".parse().unwrap();
+        assert_eq!("$9 - (3 + 2) = 4$", equation.to_latex());
+    }
+
     /*
     #[test]
     fn test_create_leading_minus() {
@@ -397,139 +2156,4 @@ mod tests {
             equation.move_n_matchsticks_for_valid_equations(1)
         );
     }*/
-
-    #[test]
-    fn build_specific_equations() {
-        let equation_pattern = EquationPattern {
-            symbol_filters: vec![
-                SymbolFilter::IsNumber,   // 0 1 2 3 4 5 6 7 8 9
-                SymbolFilter::IsOperator, // + - =
-            ],
-        };
-
-        let expected_specific_equations = vec![
-            Equation {
-                symbols: vec![Symbol::OneVar1, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::OneVar1, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::OneVar1, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::OneVar2, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::OneVar2, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::OneVar2, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Two, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Two, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Two, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Three, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Three, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Three, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar1, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar1, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar1, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar2, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar2, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::FourVar2, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Five, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Five, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Five, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Six, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Six, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Six, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Seven, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Seven, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Seven, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar1, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar1, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar1, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar2, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar2, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::EightVar2, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Nine, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Nine, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Nine, Symbol::Equal],
-            },
-            Equation {
-                symbols: vec![Symbol::Zero, Symbol::Minus],
-            },
-            Equation {
-                symbols: vec![Symbol::Zero, Symbol::Plus],
-            },
-            Equation {
-                symbols: vec![Symbol::Zero, Symbol::Equal],
-            },
-        ];
-
-        assert_eq!(
-            expected_specific_equations,
-            equation_pattern.derive_concrete_equations()
-        )
-    }
 }
@@ -0,0 +1,112 @@
+use super::Equation;
+use crate::symbol::Symbol;
+
+// number of bytes encoding the number of symbols
+const LENGTH_PREFIX_SIZE: usize = 4;
+
+/// Reasons why bytes can't be turned back into an [`Equation`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    /// There are not enough bytes for the length prefix
+    MissingLength,
+    /// The length prefix announces a different number of symbols than there are bytes
+    LengthMismatch { expected: usize, actual: usize },
+    /// The byte doesn't correspond to any [`Symbol`]
+    UnknownSymbol(u8),
+}
+
+impl Equation {
+    /// Compact binary representation of the [`Equation`] \
+    /// The number of symbols is prefixed as four little-endian bytes,
+    /// followed by one byte per [`Symbol`] holding its position in declaration order
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// let equation: Equation = "1+3=4".parse().unwrap();
+    /// let bytes = equation.to_bytes();
+    ///
+    /// assert_eq!(4 + 5, bytes.len());
+    /// assert_eq!(Ok(equation), Equation::from_bytes(&bytes));
+    /// ```
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let number_symbols = u32::try_from(self.symbols.len())
+            .expect("an equation has never more than u32::MAX symbols");
+
+        let mut bytes = Vec::with_capacity(LENGTH_PREFIX_SIZE + self.symbols.len());
+        bytes.extend(number_symbols.to_le_bytes());
+        bytes.extend(self.symbols.iter().map(|symbol| symbol.clone() as u8));
+
+        bytes
+    }
+
+    /// Reads an [`Equation`] from its binary representation (s. [`Equation::to_bytes`])
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::equation::encoding::DecodeError;
+    /// assert_eq!(Err(DecodeError::MissingLength), Equation::from_bytes(&[1, 0]));
+    /// assert_eq!(
+    ///     Err(DecodeError::UnknownSymbol(200)),
+    ///     Equation::from_bytes(&[1, 0, 0, 0, 200])
+    /// );
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let Some((length_prefix, symbol_bytes)) = bytes.split_first_chunk::<LENGTH_PREFIX_SIZE>()
+        else {
+            return Err(DecodeError::MissingLength);
+        };
+
+        let number_symbols = u32::from_le_bytes(*length_prefix) as usize;
+        if number_symbols != symbol_bytes.len() {
+            return Err(DecodeError::LengthMismatch {
+                expected: number_symbols,
+                actual: symbol_bytes.len(),
+            });
+        }
+
+        // the byte is the position of the symbol in declaration order
+        let all_symbols = Symbol::get_all();
+        let symbols = symbol_bytes
+            .iter()
+            .map(|byte| {
+                all_symbols
+                    .get(*byte as usize)
+                    .cloned()
+                    .ok_or(DecodeError::UnknownSymbol(*byte))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Equation::new_from_symbols(symbols))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_and_decode_all_symbols() {
+        let equation = Equation::new_from_symbols(Symbol::get_all());
+
+        assert_eq!(
+            Ok(equation.clone()),
+            Equation::from_bytes(&equation.to_bytes())
+        );
+    }
+
+    #[test]
+    fn encode_symbols_as_declaration_order() {
+        let equation = Equation::new_from_symbols(vec![Symbol::Minus, Symbol::Zero]);
+
+        assert_eq!(vec![2, 0, 0, 0, 0, 15], equation.to_bytes());
+    }
+
+    #[test]
+    fn reject_wrong_length() {
+        assert_eq!(
+            Err(DecodeError::LengthMismatch {
+                expected: 2,
+                actual: 1
+            }),
+            Equation::from_bytes(&[2, 0, 0, 0, 5])
+        );
+    }
+}
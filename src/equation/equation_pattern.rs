@@ -3,6 +3,15 @@ use crate::symbol::SymbolFilter;
 
 use itertools::Itertools;
 
+/// Reasons why an [`EquationPattern`] can't describe any equation
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatternError {
+    /// The pattern has no [`SymbolFilter`]s
+    EmptyPattern,
+    /// The [`SymbolFilter::List`] at this position has no symbols
+    EmptyList(usize),
+}
+
 /// Sequence of [`SymbolFilter`]s generally describing an equation
 #[derive(Clone, Debug, PartialEq)]
 pub struct EquationPattern {
@@ -15,6 +24,75 @@ impl EquationPattern {
         EquationPattern { symbol_filters }
     }
 
+    /// Checks that the [`EquationPattern`] describes at least one equation \
+    /// Otherwise deriving equations from it silently results in no equations
+    /// ```
+    /// # use matchstick::equation::{EquationPattern, PatternError};
+    /// # use matchstick::symbol::{Symbol, SymbolFilter};
+    /// let equation_pattern = EquationPattern::new_from_symbol_filters(vec![
+    ///     SymbolFilter::IsNumber,
+    ///     SymbolFilter::List(vec![Symbol::Equal]),
+    ///     SymbolFilter::IsNumber,
+    /// ]);
+    /// assert_eq!(Ok(()), equation_pattern.validate());
+    ///
+    /// let equation_pattern = EquationPattern::new_from_symbol_filters(vec![
+    ///     SymbolFilter::IsNumber,
+    ///     SymbolFilter::List(vec![]),
+    ///     SymbolFilter::IsNumber,
+    /// ]);
+    /// assert_eq!(Err(PatternError::EmptyList(1)), equation_pattern.validate());
+    /// ```
+    pub fn validate(&self) -> Result<(), PatternError> {
+        if self.symbol_filters.is_empty() {
+            return Err(PatternError::EmptyPattern);
+        }
+
+        for (position, symbol_filter) in self.symbol_filters.iter().enumerate() {
+            if let SymbolFilter::List(symbols) = symbol_filter
+                && symbols.is_empty()
+            {
+                return Err(PatternError::EmptyList(position));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Consumes the [`EquationPattern`] and hands over its [`SymbolFilter`]s, e.g. to combine patterns
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::symbol::SymbolFilter;
+    /// let equation_pattern: EquationPattern = "NO".parse().unwrap();
+    /// assert_eq!(
+    ///     vec![SymbolFilter::IsNumber, SymbolFilter::IsOperator],
+    ///     equation_pattern.into_symbol_filters()
+    /// );
+    /// ```
+    pub fn into_symbol_filters(self) -> Vec<SymbolFilter> {
+        self.symbol_filters
+    }
+
+    // The filter for each position of the equation
+    pub(crate) fn get_symbol_filters(&self) -> &[SymbolFilter] {
+        &self.symbol_filters
+    }
+
+    /// How many equations the [`EquationPattern`] describes, without forming them \
+    /// Saturates at [`u128::MAX`]
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// // ten digits with two variants each of one, four and eight
+    /// let equation_pattern: EquationPattern = "N[+-]N".parse().unwrap();
+    /// assert_eq!(13 * 2 * 13, equation_pattern.count_concrete_equations());
+    /// ```
+    pub fn count_concrete_equations(&self) -> u128 {
+        self.symbol_filters
+            .iter()
+            .map(|filter| filter.get_corresponding_symbols().len() as u128)
+            .fold(1, u128::saturating_mul)
+    }
+
     pub(crate) fn derive_concrete_equations(&self) -> Vec<Equation> {
         let mut symbols_for_positions = Vec::new();
 
@@ -45,6 +123,49 @@ mod test {
     use super::*;
     use crate::symbol::Symbol;
 
+    #[test]
+    fn reject_empty_pattern() {
+        let equation_pattern = EquationPattern {
+            symbol_filters: vec![],
+        };
+
+        assert_eq!(Err(PatternError::EmptyPattern), equation_pattern.validate());
+    }
+
+    #[test]
+    fn derive_equations_with_negative_operand() {
+        let equation_pattern = EquationPattern::new_from_symbol_filters(
+            [
+                SymbolFilter::negative_number(1),
+                "+N=N"
+                    .parse::<EquationPattern>()
+                    .unwrap()
+                    .into_symbol_filters(),
+            ]
+            .concat(),
+        );
+        let equations = equation_pattern.derive_concrete_equations();
+
+        let equation: Equation = "-3+5=2".parse().unwrap();
+        assert!(equations.contains(&equation));
+        assert_eq!(Ok(()), equation.mathematically_validate());
+        assert!(
+            equations
+                .iter()
+                .all(|equation| equation.get_symbols()[..2] != [Symbol::Minus, Symbol::Zero])
+        );
+    }
+
+    #[test]
+    fn count_like_derived_equations() {
+        let equation_pattern: EquationPattern = "NG[+-]=".parse().unwrap();
+
+        assert_eq!(
+            equation_pattern.derive_concrete_equations().len() as u128,
+            equation_pattern.count_concrete_equations()
+        );
+    }
+
     #[test]
     fn build_specific_equations() {
         let equation_pattern = EquationPattern {
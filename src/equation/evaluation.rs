@@ -0,0 +1,90 @@
+use evalexpr::{EvalexprError, Value, eval_int};
+
+use super::validation::ValidationError;
+
+/// Reasons why an expression can't be evaluated
+#[derive(Clone, Debug, PartialEq)]
+pub enum EvalError {
+    /// The expression is malformed, e.g. "2+" or "(3"
+    InvalidExpression,
+    /// A number or a result is too large to be evaluated
+    Overflow,
+}
+
+impl From<EvalError> for ValidationError {
+    fn from(eval_error: EvalError) -> Self {
+        match eval_error {
+            EvalError::InvalidExpression => ValidationError::InvalidExpression,
+            EvalError::Overflow => ValidationError::Overflow,
+        }
+    }
+}
+
+/// Evaluates a single expression without equal sign, like "12-3", to an integer \
+/// Implement this to validate equations with different arithmetic
+/// (s. [`Equation::validate_with_evaluator`](super::Equation::validate_with_evaluator))
+/// ```
+/// # use matchstick::equation::Equation;
+/// # use matchstick::equation::evaluation::{EvalError, Evaluator, IntEvaluator};
+/// # use matchstick::equation::validation::ValidationOptions;
+/// // compares the results modulo 10
+/// struct ModuloTenEvaluator;
+///
+/// impl Evaluator for ModuloTenEvaluator {
+///     fn evaluate(&self, expression: &str) -> Result<i64, EvalError> {
+///         IntEvaluator
+///             .evaluate(expression)
+///             .map(|value| value.rem_euclid(10))
+///     }
+/// }
+///
+/// let equation: Equation = "7+5=2".parse().unwrap();
+/// assert!(equation.mathematically_validate().is_err());
+/// assert_eq!(
+///     Ok(()),
+///     equation.validate_with_evaluator(&ValidationOptions::default(), &ModuloTenEvaluator)
+/// );
+/// ```
+pub trait Evaluator {
+    /// Value of the expression
+    fn evaluate(&self, expression: &str) -> Result<i64, EvalError>;
+}
+
+/// The default [`Evaluator`] using integer arithmetic \
+/// Numbers or results that don't fit into an i64 are reported instead of wrapping around
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct IntEvaluator;
+
+impl Evaluator for IntEvaluator {
+    fn evaluate(&self, expression: &str) -> Result<i64, EvalError> {
+        eval_int(expression).map_err(|error| match error {
+            EvalexprError::ExpectedInt {
+                actual: Value::Float(_),
+            } // too large numbers are read as floats
+            | EvalexprError::AdditionError { .. }
+            | EvalexprError::SubtractionError { .. }
+            | EvalexprError::MultiplicationError { .. }
+            | EvalexprError::NegationError { .. } => EvalError::Overflow,
+            _ => EvalError::InvalidExpression,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_integer_expressions() {
+        assert_eq!(Ok(9), IntEvaluator.evaluate("12-3"));
+        assert_eq!(Ok(-1), IntEvaluator.evaluate("2+(-3)"));
+        assert_eq!(
+            Err(EvalError::InvalidExpression),
+            IntEvaluator.evaluate("2+")
+        );
+        assert_eq!(
+            Err(EvalError::Overflow),
+            IntEvaluator.evaluate("9223372036854775807+1")
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::str::FromStr;
+
+use itertools::Itertools;
+
+use super::{Equation, EquationPattern};
+use crate::symbol::{Symbol, SymbolFilter};
+
+/// Reasons why a text can't be turned into an [`Equation`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParseError {
+    /// The character has no corresponding [`Symbol`]
+    UnknownCharacter(char),
+    /// A list of symbols in a pattern is opened with "[" but never closed with "]"
+    UnclosedList,
+    /// A riddle line has no number of matchstick movements after the equation
+    MissingMoveCount,
+    /// A riddle line has more than two words, e.g. because the equation contains whitespace
+    InvalidRiddleLine(String),
+    /// The number of matchstick movements of a riddle line is not a number
+    InvalidMoveCount(String),
+    /// Reading the riddle lines failed
+    ReadFailed(std::io::ErrorKind),
+}
+
+// all variants of the symbol shown by the character
+fn symbols_for_character(character: char) -> Result<Vec<Symbol>, ParseError> {
+    let symbols = Symbol::get_all()
+        .into_iter()
+        .filter(|symbol| symbol.to_str() == character.to_string())
+        .collect::<Vec<_>>();
+
+    match symbols.is_empty() {
+        true => Err(ParseError::UnknownCharacter(character)),
+        false => Ok(symbols),
+    }
+}
+
+impl FromStr for Equation {
+    type Err = ParseError;
+
+    /// Every character is turned into its [`Symbol`], whitespace is ignored \
+    /// Digits with several variants use the first variant, e.g. [`Symbol::OneVar1`]
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation: Equation = "1 + 3 = 4".parse().unwrap();
+    /// assert_eq!(
+    ///     equation,
+    ///     Equation::new_from_symbols(vec![
+    ///         Symbol::OneVar1,
+    ///         Symbol::Plus,
+    ///         Symbol::Three,
+    ///         Symbol::Equal,
+    ///         Symbol::FourVar1,
+    ///     ])
+    /// );
+    /// ```
+    fn from_str(equation_string: &str) -> Result<Self, Self::Err> {
+        let mut symbols = Vec::new();
+        for character in equation_string
+            .chars()
+            .filter(|character| !character.is_whitespace())
+        {
+            // first declared variant of a symbol is the default one
+            let mut symbol_variants = symbols_for_character(character)?;
+            symbols.push(symbol_variants.swap_remove(0));
+        }
+
+        Ok(Equation::new_from_symbols(symbols))
+    }
+}
+
+impl FromStr for EquationPattern {
+    type Err = ParseError;
+
+    /// Every character describes the [`SymbolFilter`] of one position, whitespace is ignored
+    /// - `N` is [`SymbolFilter::IsNumber`]
+    /// - `O` is [`SymbolFilter::IsOperator`]
+    /// - `G` is [`SymbolFilter::IsGrouping`]
+    /// - `D` is [`SymbolFilter::IsDecorative`]
+    /// - `*` is [`SymbolFilter::IsAny`]
+    /// - a symbol like `=` or `4` is a [`SymbolFilter::List`] with all its variants
+    /// - symbols in brackets like `[+-]` are a [`SymbolFilter::List`] with all their variants
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::symbol::{Symbol, SymbolFilter};
+    /// let equation_pattern: EquationPattern = "N[+-]N=1".parse().unwrap();
+    /// assert_eq!(
+    ///     equation_pattern,
+    ///     EquationPattern::new_from_symbol_filters(vec![
+    ///         SymbolFilter::IsNumber,
+    ///         SymbolFilter::List(vec![Symbol::Plus, Symbol::Minus]),
+    ///         SymbolFilter::IsNumber,
+    ///         SymbolFilter::List(vec![Symbol::Equal]),
+    ///         SymbolFilter::List(vec![Symbol::OneVar1, Symbol::OneVar2]),
+    ///     ])
+    /// );
+    /// ```
+    fn from_str(pattern_string: &str) -> Result<Self, Self::Err> {
+        let mut symbol_filters = Vec::new();
+        let mut characters = pattern_string
+            .chars()
+            .filter(|character| !character.is_whitespace());
+        while let Some(character) = characters.next() {
+            let symbol_filter = match character {
+                'N' => SymbolFilter::IsNumber,
+                'O' => SymbolFilter::IsOperator,
+                'G' => SymbolFilter::IsGrouping,
+                'D' => SymbolFilter::IsDecorative,
+                '*' => SymbolFilter::IsAny,
+                '[' => {
+                    // collect symbols until the list is closed
+                    let mut symbols = Vec::new();
+                    loop {
+                        match characters.next() {
+                            None => return Err(ParseError::UnclosedList),
+                            Some(']') => break,
+                            Some(listed_character) => {
+                                symbols.append(&mut symbols_for_character(listed_character)?)
+                            }
+                        }
+                    }
+                    SymbolFilter::List(symbols)
+                }
+                _ => SymbolFilter::List(symbols_for_character(character)?),
+            };
+            symbol_filters.push(symbol_filter);
+        }
+
+        Ok(EquationPattern::new_from_symbol_filters(symbol_filters))
+    }
+}
+
+impl EquationPattern {
+    /// Text of the [`EquationPattern`] in the notation read by its [`FromStr`] implementation \
+    /// [`SymbolFilter::IsNonZeroNumber`] and [`SymbolFilter::List`]s are written as their characters,
+    /// so different variants of the same digit can't be told apart
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::symbol::{Symbol, SymbolFilter};
+    /// let equation_pattern = EquationPattern::new_from_symbol_filters(vec![
+    ///     SymbolFilter::IsNonZeroNumber,
+    ///     SymbolFilter::IsOperator,
+    ///     SymbolFilter::List(vec![Symbol::OneVar1, Symbol::Two]),
+    ///     SymbolFilter::List(vec![Symbol::Equal]),
+    ///     SymbolFilter::IsAny,
+    /// ]);
+    /// assert_eq!("[123456789]O[12]=*", equation_pattern.to_pattern_string());
+    /// ```
+    pub fn to_pattern_string(&self) -> String {
+        self.symbol_filters
+            .iter()
+            .map(|symbol_filter| match symbol_filter {
+                SymbolFilter::IsAny => "*".to_string(),
+                SymbolFilter::IsNumber => "N".to_string(),
+                SymbolFilter::IsOperator => "O".to_string(),
+                SymbolFilter::IsGrouping => "G".to_string(),
+                SymbolFilter::IsDecorative => "D".to_string(),
+                SymbolFilter::IsNonZeroNumber | SymbolFilter::List(_) => {
+                    let characters = symbol_filter
+                        .get_corresponding_symbols()
+                        .iter()
+                        .map(Symbol::to_str)
+                        .unique()
+                        .collect::<String>();
+
+                    // a single character doesn't need brackets
+                    match characters.chars().count() {
+                        1 => characters,
+                        _ => format!("[{characters}]"),
+                    }
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::equation::validation::ValidationError;
+
+    #[test]
+    fn parse_all_characters() {
+        let expected_equation = Equation {
+            symbols: vec![
+                Symbol::Minus,
+                Symbol::Plus,
+                Symbol::Equal,
+                Symbol::OneVar1,
+                Symbol::Two,
+                Symbol::Three,
+                Symbol::FourVar1,
+                Symbol::Five,
+                Symbol::Six,
+                Symbol::Seven,
+                Symbol::EightVar1,
+                Symbol::Nine,
+                Symbol::Zero,
+            ],
+        };
+
+        assert_eq!(Ok(expected_equation), "-+=1234567890".parse());
+    }
+
+    #[test]
+    fn parse_long_operand_with_whitespace() {
+        let equation_string = format!(" {} + 1 =\t2 ", "1".repeat(40));
+        let equation: Equation = equation_string.parse().unwrap();
+
+        assert_eq!(44, equation.symbols.len());
+        assert_eq!(
+            Err(ValidationError::Overflow),
+            equation.mathematically_validate()
+        );
+    }
+
+    #[test]
+    fn reject_unknown_character() {
+        assert_eq!(
+            Err(ParseError::UnknownCharacter('x')),
+            "2x3=6".parse::<Equation>()
+        );
+    }
+
+    #[test]
+    fn parse_pattern() {
+        let expected_pattern = EquationPattern::new_from_symbol_filters(vec![
+            SymbolFilter::IsAny,
+            SymbolFilter::IsOperator,
+            SymbolFilter::List(vec![Symbol::EightVar1, Symbol::EightVar2, Symbol::Zero]),
+            SymbolFilter::List(vec![Symbol::Equal]),
+            SymbolFilter::IsNumber,
+        ]);
+
+        assert_eq!(Ok(expected_pattern), "*O[80]=N".parse());
+    }
+
+    #[test]
+    fn reject_unclosed_list_in_pattern() {
+        assert_eq!(
+            Err(ParseError::UnclosedList),
+            "N=N[+-".parse::<EquationPattern>()
+        );
+    }
+
+    #[test]
+    fn pattern_string_round_trip() {
+        let pattern_string = "N O [+-] * = 1 [34]";
+        let equation_pattern: EquationPattern = pattern_string.parse().unwrap();
+
+        assert_eq!("NO[+-]*=1[34]", equation_pattern.to_pattern_string());
+        assert_eq!(
+            Ok(equation_pattern.clone()),
+            equation_pattern.to_pattern_string().parse()
+        );
+    }
+}
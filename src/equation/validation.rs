@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use super::Equation;
+
+/// Options to adjust how strictly an [`Equation`] is mathematically validated
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ValidationOptions {
+    /// Whether multi-digit numbers may start with zero, like "05"
+    pub allow_leading_zeros: bool,
+    /// Whether only one equal sign is allowed, rejecting chained equalities like "2=2=2"
+    pub require_single_equals: bool,
+    /// Whether numbers and operators must strictly alternate before evaluating,
+    /// allowing a negation only at the start of an expression
+    pub strict_alternation: bool,
+}
+
+impl Default for ValidationOptions {
+    fn default() -> Self {
+        ValidationOptions {
+            allow_leading_zeros: true,
+            require_single_equals: false,
+            strict_alternation: false,
+        }
+    }
+}
+
+/// Reasons why an [`Equation`] is not mathematically valid
+#[derive(Clone, Debug, PartialEq)]
+pub enum ValidationError {
+    /// The equation has no symbols at all
+    EmptyExpression,
+    /// There is no equal sign separating at least two expressions
+    MissingEqualSign,
+    /// An expression can't be evaluated
+    InvalidExpression,
+    /// A number or a result is too large to be evaluated
+    Overflow,
+    /// The expressions are evaluated to different values
+    UnequalExpressions,
+    /// A multi-digit number starts with zero while this is not allowed
+    LeadingZero,
+    /// There is more than one equal sign while this is not allowed
+    MultipleEqualSigns,
+    /// Numbers and operators don't strictly alternate while this is required
+    BrokenAlternation,
+    /// An operator lacks an operand on one of its sides, like "2+=2" or "=2+3"
+    DanglingOperator,
+}
+
+// Remembers validation results of equations with the same text during one solve run,
+// so equations formed several times are only evaluated once
+// All results depend on the options given, so a cache is only used with one set of options
+#[derive(Debug)]
+pub(crate) struct ValidationCache {
+    options: ValidationOptions,
+    results: HashMap<String, Result<(), ValidationError>>,
+}
+
+impl ValidationCache {
+    pub(crate) fn new(options: ValidationOptions) -> Self {
+        ValidationCache {
+            options,
+            results: HashMap::new(),
+        }
+    }
+
+    // like [`Equation::validate_with_options`] but evaluated only once per text
+    pub(crate) fn validate(&mut self, equation: &Equation) -> Result<(), ValidationError> {
+        self.results
+            .entry(equation.to_plain_text())
+            .or_insert_with(|| equation.validate_with_options(&self.options))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn evaluate_equal_texts_once() {
+        // "9 - 5 = 4" forms several equations with the same text with two matchstick movements
+        let riddle_equation: Equation = "9-5=4".parse().unwrap();
+        let formed_equations = riddle_equation.move_n_matchsticks(2);
+
+        let mut validation_cache = ValidationCache::new(ValidationOptions::default());
+        let results = formed_equations
+            .iter()
+            .map(|equation| validation_cache.validate(equation))
+            .collect::<Vec<_>>();
+
+        let expected_results = formed_equations
+            .iter()
+            .map(|equation| equation.mathematically_validate())
+            .collect::<Vec<_>>();
+        assert_eq!(expected_results, results);
+        assert!(validation_cache.results.len() < formed_equations.len());
+    }
+}
@@ -1,6 +1,5 @@
 pub mod equation;
 pub mod puzzle;
+pub mod segment_display;
 pub mod symbol;
-
-mod segment_display;
-mod transition;
+pub mod transition;
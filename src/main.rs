@@ -1,53 +1,109 @@
+use std::env;
+use std::process;
+
 use matchstick::equation::{Equation, EquationPattern};
 use matchstick::puzzle::{Puzzle, puzzle_generator::PuzzleGenerator, riddle::Riddle};
-use matchstick::symbol::{Symbol, SymbolFilter};
+
+const USAGE: &str = "Usage:
+    matchstick <equation> [--moves <number>]
+    matchstick --generate <pattern> [--solutions <number>] [--moves <number>]
+
+An equation consists of digits and the operators + - =, e.g. \"2-7=3\"
+A pattern describes each position with N (any number), O (any operator),
+* (any symbol), a concrete symbol, or a list of symbols in brackets, e.g. \"NO[12]=N\"";
+
+struct Arguments {
+    input: String,
+    number_matchstick_movements: usize,
+    generate: bool,
+    number_solutions: usize,
+}
+
+fn parse_arguments(mut arguments: impl Iterator<Item = String>) -> Result<Arguments, String> {
+    let mut input = None;
+    let mut number_matchstick_movements = 1;
+    let mut generate = false;
+    let mut number_solutions = 1;
+
+    while let Some(argument) = arguments.next() {
+        match argument.as_str() {
+            "--generate" => generate = true,
+            "--moves" => number_matchstick_movements = parse_number(arguments.next(), "--moves")?,
+            "--solutions" => number_solutions = parse_number(arguments.next(), "--solutions")?,
+            _ if input.is_none() => input = Some(argument),
+            _ => return Err(format!("Unexpected argument \"{argument}\"")),
+        }
+    }
+
+    let Some(input) = input else {
+        return Err("Missing equation or pattern".to_string());
+    };
+
+    Ok(Arguments {
+        input,
+        number_matchstick_movements,
+        generate,
+        number_solutions,
+    })
+}
+
+fn parse_number(argument: Option<String>, flag: &str) -> Result<usize, String> {
+    argument
+        .and_then(|argument| argument.parse().ok())
+        .ok_or(format!("{flag} requires a number"))
+}
+
+fn solve(arguments: &Arguments) -> Result<(), String> {
+    let riddle_equation: Equation = arguments
+        .input
+        .parse()
+        .map_err(|error| format!("Invalid equation: {error:?}"))?;
+    let puzzle = Puzzle::solved_from_riddle(Riddle::new(
+        riddle_equation,
+        arguments.number_matchstick_movements,
+    ));
+
+    println!("{puzzle}");
+
+    Ok(())
+}
+
+fn generate(arguments: &Arguments) -> Result<(), String> {
+    let riddle_equation_pattern: EquationPattern = arguments
+        .input
+        .parse()
+        .map_err(|error| format!("Invalid pattern: {error:?}"))?;
+    let puzzle_generator = PuzzleGenerator::try_new(
+        riddle_equation_pattern,
+        arguments.number_matchstick_movements,
+    )
+    .map_err(|error| format!("Invalid pattern: {error:?}"))?;
+    let generated_puzzles =
+        puzzle_generator.derive_puzzles_with_n_solutions(arguments.number_solutions);
+
+    println!("Generated {} puzzle(s):\n", generated_puzzles.len());
+    for puzzle in generated_puzzles {
+        println!("{puzzle}\n");
+    }
+
+    Ok(())
+}
 
 fn main() {
-    /*
-    let equation = Equation {
-        symbols_for_position: vec![
-            Symbol::Minus,
-            Symbol::Plus,
-            Symbol::Equal,
-            Symbol::Zero,
-            Symbol::OneVar1,
-            Symbol::OneVar2,
-            Symbol::Two,
-            Symbol::Three,
-            Symbol::FourVar1,
-            Symbol::FourVar2,
-            Symbol::Five,
-            Symbol::Six,
-            Symbol::Seven,
-            Symbol::EightVar1,
-            Symbol::EightVar2,
-            Symbol::Nine,
-        ],
+    let arguments = match parse_arguments(env::args().skip(1)) {
+        Ok(arguments) => arguments,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            process::exit(1);
+        }
+    };
+
+    let result = match arguments.generate {
+        true => generate(&arguments),
+        false => solve(&arguments),
     };
-    println!("{}", equation.to_segment_displays());
-    */
-
-    let riddle_equation = Equation::new_from_symbols(vec![
-        Symbol::Two,
-        Symbol::Minus,
-        Symbol::Seven,
-        Symbol::Equal,
-        Symbol::Three,
-    ]);
-    let mut my_puzzle = Puzzle::new_from_riddle(Riddle::new(riddle_equation, 1));
-    my_puzzle.search_and_set_solution();
-
-    println!("My puzzle: {:#?}", my_puzzle);
-
-    let riddle_equation_pattern = EquationPattern::new_from_symbol_filters(vec![
-        SymbolFilter::IsNumber,
-        SymbolFilter::IsOperator,
-        SymbolFilter::IsNumber,
-        SymbolFilter::List(vec![Symbol::Equal]),
-        SymbolFilter::IsNumber,
-    ]);
-    let my_puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 2);
-    let generated_puzzles = my_puzzle_generator.derive_puzzles_with_n_solutions(1);
-
-    println!("Generated puzzles: {:#?}", generated_puzzles);
+    if let Err(message) = result {
+        eprintln!("{message}");
+        process::exit(1);
+    }
 }
@@ -1,10 +1,74 @@
 pub mod puzzle_generator;
 pub mod riddle;
 pub mod solution;
+pub mod text_block;
 
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use itertools::Itertools;
+
+use crate::equation::parse::ParseError;
+use crate::equation::{Equation, EquationPattern};
+use crate::transition::{MovePattern, TransitionSequenceCache};
 use riddle::Riddle;
 use solution::SolutionWrapper;
 
+/// Solves the riddle equation given as text with the given number of matchstick movements
+/// and returns the solution equations as text \
+/// This avoids handing [`Equation`]s across boundaries like WebAssembly
+/// ```
+/// # use matchstick::puzzle::solve_riddle_strings;
+/// assert_eq!(Ok(vec!["1+3=4".to_string()]), solve_riddle_strings("7-3=4", 1));
+/// ```
+pub fn solve_riddle_strings(
+    equation: &str,
+    number_matchstick_movements: usize,
+) -> Result<Vec<String>, ParseError> {
+    let riddle_equation: Equation = equation.parse()?;
+    let puzzle =
+        Puzzle::solved_from_riddle(Riddle::new(riddle_equation, number_matchstick_movements));
+
+    let solution_strings = match puzzle.wrapped_solution.get_inner_reference() {
+        Ok(solution) => solution
+            .get_solution_equations()
+            .iter()
+            .map(Equation::to_plain_text)
+            .collect(),
+        Err(()) => Vec::new(),
+    };
+
+    Ok(solution_strings)
+}
+
+/// Programmatically searches the solutions of all [`Riddle`]s \
+/// The matchstick movement patterns are only generated once for all riddles
+/// with the same number of symbols and matchstick movements
+/// ```
+/// # use matchstick::puzzle::riddle::Riddle;
+/// # use matchstick::puzzle::solution::SolutionWrapper;
+/// # use matchstick::puzzle::solve_batch;
+/// let riddles = vec![
+///     Riddle::new("7-3=4".parse().unwrap(), 1),
+///     Riddle::new("3+3=5".parse().unwrap(), 1),
+/// ];
+/// let wrapped_solutions = solve_batch(&riddles);
+///
+/// assert_eq!(2, wrapped_solutions.len());
+/// assert!(matches!(
+///     wrapped_solutions[1],
+///     SolutionWrapper::ProgrammaticallySet(_)
+/// ));
+/// ```
+pub fn solve_batch(riddles: &[Riddle]) -> Vec<SolutionWrapper> {
+    let mut transition_sequence_cache = TransitionSequenceCache::default();
+
+    riddles
+        .iter()
+        .map(|riddle| riddle.solve_with_cache(&mut transition_sequence_cache))
+        .collect()
+}
+
 /// Holds the [`Riddle`] and the [`SolutionWrapper`] containing the [`solution::Solution`]
 #[derive(Debug, PartialEq)]
 pub struct Puzzle {
@@ -42,6 +106,53 @@ impl Puzzle {
         }
     }
 
+    /// Creates new [`Puzzle`] from riddle and programmatically sets its solution right away
+    /// (s. [`Puzzle::search_and_set_solution`])
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// // Riddle equation is "7 - 3 = 4" and one matchstick must be moved
+    /// let puzzle = Puzzle::solved_from_riddle(Riddle::new("7-3=4".parse().unwrap(), 1));
+    ///
+    /// assert!(matches!(
+    ///     puzzle.get_wrapped_solution(),
+    ///     SolutionWrapper::ProgrammaticallySet(_)
+    /// ));
+    /// ```
+    pub fn solved_from_riddle(riddle: Riddle) -> Self {
+        let mut puzzle = Puzzle::new_from_riddle(riddle);
+        puzzle.search_and_set_solution();
+        puzzle
+    }
+
+    /// Creates an unsolved [`Puzzle`] for each riddle equation matching the [`EquationPattern`],
+    /// e.g. to solve them later on other threads \
+    /// Deriving the riddle equations is cheap compared to solving them
+    /// (s. [`Puzzle::search_and_set_solution`])
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// let pattern: EquationPattern = "N+N=7".parse().unwrap();
+    /// let puzzles = Puzzle::unsolved_from_pattern(&pattern, 1);
+    ///
+    /// // 13 * 13 riddle equations as all variants of a digit are used
+    /// assert_eq!(169, puzzles.len());
+    /// assert!(
+    ///     puzzles
+    ///         .iter()
+    ///         .all(|puzzle| puzzle.get_wrapped_solution() == &SolutionWrapper::NotYetSet)
+    /// );
+    /// ```
+    pub fn unsolved_from_pattern(pattern: &EquationPattern, moves: usize) -> Vec<Self> {
+        pattern
+            .derive_concrete_equations()
+            .into_iter()
+            .map(|riddle_equation| Puzzle::new_from_riddle(Riddle::new(riddle_equation, moves)))
+            .collect()
+    }
+
     /// Programmatically find solution in form of [`crate::equation::Equation`]s fitting to the [`Riddle`] of this [`Puzzle`]\
     /// The found solution is set\
     /// Returns number of found solution [`crate::equation::Equation`]s
@@ -88,6 +199,140 @@ impl Puzzle {
         }
     }
 
+    /// Number of solution [`Equation`]s that differ in their mathematical text \
+    /// Solutions only differing in symbol variants, like [`crate::symbol::Symbol::OneVar1`]
+    /// and [`crate::symbol::Symbol::OneVar2`], count as one. Without a set solution this is 0
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // Riddle equation is "1 = +" and two matchsticks must be moved
+    /// let mut puzzle = Puzzle::new_from_riddle(Riddle::new("1=+".parse().unwrap(), 2));
+    ///
+    /// // "1 = 1" is found twice using both variants of the second one
+    /// assert_eq!(2, puzzle.search_and_set_solution());
+    /// assert_eq!(1, puzzle.distinct_solution_count());
+    /// ```
+    pub fn distinct_solution_count(&self) -> usize {
+        match self.wrapped_solution.get_inner_reference() {
+            Ok(solution) => solution
+                .get_solution_equations()
+                .iter()
+                .map(Equation::to_plain_text)
+                .unique()
+                .count(),
+            Err(()) => 0,
+        }
+    }
+
+    /// Solution [`Equation`]s grouped by how their matchsticks were moved
+    /// (s. [`crate::transition::TransitionSequence::move_pattern`]) \
+    /// The movements of each solution are recovered from the riddle equation.
+    /// Solutions of a different length than the riddle equation, which can only be set manually,
+    /// are left out. Without a set solution the map is empty
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::transition::MovePattern;
+    /// let puzzle = Puzzle::solved_from_riddle(Riddle::new("7-3=4".parse().unwrap(), 1));
+    /// let solutions_by_move_pattern = puzzle.solutions_by_move_pattern();
+    ///
+    /// // One matchstick moves from seven to minus changing to one and plus
+    /// assert_eq!(1, solutions_by_move_pattern[&MovePattern::BetweenSymbols].len());
+    /// assert!(!solutions_by_move_pattern.contains_key(&MovePattern::WithinSymbols));
+    /// ```
+    pub fn solutions_by_move_pattern(&self) -> HashMap<MovePattern, Vec<&Equation>> {
+        let mut solutions_by_move_pattern: HashMap<MovePattern, Vec<&Equation>> = HashMap::new();
+        let Ok(solution) = self.wrapped_solution.get_inner_reference() else {
+            return solutions_by_move_pattern;
+        };
+
+        for solution_equation in solution.get_solution_equations() {
+            if let Some(transition_sequence) = self
+                .riddle
+                .get_riddle_equation()
+                .transition_to(solution_equation)
+            {
+                solutions_by_move_pattern
+                    .entry(transition_sequence.move_pattern())
+                    .or_default()
+                    .push(solution_equation);
+            }
+        }
+
+        solutions_by_move_pattern
+    }
+
+    /// Distinct solution [`Equation`]s sorted by their text (s. [`solution::Solution::signature`]) \
+    /// Solutions only differing in symbol variants are returned once,
+    /// using the first variant of each digit like when parsing an [`Equation`].
+    /// Without a set solution this is empty
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "1 = 1" is found twice using both variants of the second one
+    /// let puzzle = Puzzle::solved_from_riddle(Riddle::new("1=+".parse().unwrap(), 2));
+    /// assert_eq!(
+    ///     vec!["1=1".parse::<Equation>().unwrap()],
+    ///     puzzle.unique_sorted_solutions()
+    /// );
+    /// ```
+    pub fn unique_sorted_solutions(&self) -> Vec<Equation> {
+        match self.wrapped_solution.get_inner_reference() {
+            Ok(solution) => solution
+                .signature()
+                .into_iter()
+                .map(|canonical_string| {
+                    canonical_string
+                        .parse()
+                        .expect("text of symbols should be readable as equation")
+                })
+                .collect(),
+            Err(()) => Vec::new(),
+        }
+    }
+
+    /// Whether both [`Puzzle`]s mean the same, unlike the strict comparison with `==` \
+    /// Riddle equations and solution equations are compared by their mathematical text,
+    /// so symbol variants don't matter, and the solutions regardless of their order.
+    /// The number of matchstick movements must be the same and either both or none
+    /// of the solutions must be set
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::symbol::Symbol;
+    /// let puzzle = Puzzle::solved_from_riddle(Riddle::new("1=+".parse().unwrap(), 2));
+    /// // "1 = +" with the other variant of one
+    /// let riddle_equation =
+    ///     Equation::new_from_symbols(vec![Symbol::OneVar2, Symbol::Equal, Symbol::Plus]);
+    /// let other_puzzle = Puzzle::solved_from_riddle(Riddle::new(riddle_equation, 2));
+    ///
+    /// assert_ne!(puzzle, other_puzzle);
+    /// assert!(puzzle.is_equivalent(&other_puzzle));
+    /// ```
+    pub fn is_equivalent(&self, other: &Puzzle) -> bool {
+        let distinct_solution_texts = |puzzle: &Puzzle| {
+            puzzle
+                .wrapped_solution
+                .get_inner_reference()
+                .ok()
+                .map(|solution| {
+                    solution
+                        .get_solution_equations()
+                        .iter()
+                        .map(Equation::to_plain_text)
+                        .collect::<HashSet<_>>()
+                })
+        };
+
+        self.riddle.get_riddle_equation().to_plain_text()
+            == other.riddle.get_riddle_equation().to_plain_text()
+            && self.riddle.get_number_matchstick_movements()
+                == other.riddle.get_number_matchstick_movements()
+            && distinct_solution_texts(self) == distinct_solution_texts(other)
+    }
+
     /// The [`solution::Solution`] of the [`Puzzle`] is set to given, arbitrary value
     /// ```
     /// # use matchstick::equation::Equation;
@@ -127,6 +372,43 @@ impl Puzzle {
         self.wrapped_solution = wrapped_solution;
     }
 
+    /// Checks that each solution equation, e.g. set by [`Puzzle::manually_set_solution`],
+    /// actually solves the riddle (s. [`Riddle::accepts`]) \
+    /// The equations that don't are returned. Without a set solution there is nothing to check
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// // "7 - 3 = 4" is solved by "1 + 3 = 4" with one matchstick movement
+    /// let mut puzzle = Puzzle::new_from_riddle(Riddle::new("7-3=4".parse().unwrap(), 1));
+    /// let solution_equation: Equation = "1+3=4".parse().unwrap();
+    /// let wrong_equation: Equation = "7+3=4".parse().unwrap();
+    ///
+    /// puzzle.manually_set_solution(SolutionWrapper::new_manually_set_solution(vec![
+    ///     solution_equation,
+    ///     wrong_equation.clone(),
+    /// ]));
+    /// assert_eq!(Err(vec![wrong_equation]), puzzle.verify_manual_solution());
+    /// ```
+    pub fn verify_manual_solution(&self) -> Result<(), Vec<Equation>> {
+        let Ok(solution) = self.wrapped_solution.get_inner_reference() else {
+            return Ok(());
+        };
+
+        let wrong_equations = solution
+            .get_solution_equations()
+            .iter()
+            .filter(|solution_equation| !self.riddle.accepts(solution_equation))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        match wrong_equations.is_empty() {
+            true => Ok(()),
+            false => Err(wrong_equations),
+        }
+    }
+
     /// Getter function for [`Riddle`]  
     pub fn get_riddle(&self) -> &Riddle {
         &self.riddle
@@ -141,15 +423,93 @@ impl Puzzle {
     pub fn set_riddle(&mut self, riddle: Riddle) {
         self.riddle = riddle
     }
+
+    /// Drawing of [`Puzzle`] showing the riddle equation, the number of matchstick movements
+    /// and, if set, the solution equations
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // Riddle equation is "3 = 8" and one matchstick must be moved
+    /// let mut puzzle = Puzzle::new_from_riddle(Riddle::new("3=8".parse().unwrap(), 1));
+    /// puzzle.search_and_set_solution();
+    ///
+    /// assert_eq!(puzzle.draw(),
+    #[doc = "\"Riddle (move 1 matchstick(s)):"]
+    #[doc = " ___       ___ "]
+    #[doc = "    | _ _ |   |"]
+    #[doc = " _ _| _ _ |_ _|"]
+    #[doc = "    |     |   |"]
+    #[doc = " ___|     |___|"]
+    #[doc = ""]
+    #[doc = "Solution 1:"]
+    #[doc = " ___       ___ "]
+    #[doc = "|   | _ _ |   |"]
+    #[doc = "|_ _| _ _ |_ _|"]
+    #[doc = "    |         |"]
+    #[doc = " ___|      ___|\");"]
+    /// ```
+    pub fn draw(&self) -> String {
+        let mut drawing = format!(
+            "Riddle (move {} matchstick(s)):\n{}",
+            self.riddle.get_number_matchstick_movements(),
+            self.riddle.get_riddle_equation().draw()
+        );
+
+        match &self.wrapped_solution {
+            SolutionWrapper::NotYetSet => drawing.push_str("\n\nSolution not set yet"),
+            SolutionWrapper::ProgrammaticallySet(solution)
+            | SolutionWrapper::ManuallySet(solution) => {
+                if solution.get_solution_equations().is_empty() {
+                    drawing.push_str("\n\nNo solution");
+                }
+                for (index, solution_equation) in
+                    solution.get_solution_equations().iter().enumerate()
+                {
+                    drawing.push_str(&format!(
+                        "\n\nSolution {}:\n{}",
+                        index + 1,
+                        solution_equation.draw()
+                    ));
+                }
+            }
+        }
+
+        drawing
+    }
+}
+
+impl fmt::Display for Puzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.draw())
+    }
 }
 
 #[cfg(test)]
 mod test {
-    use crate::equation::Equation;
     use crate::symbol::Symbol;
 
     use super::*;
 
+    #[test]
+    fn equivalent_regardless_of_solution_order() {
+        let mut riddle = Riddle::new("6+4=4".parse().unwrap(), 1);
+        let puzzle = Puzzle::solved_from_riddle(riddle.clone());
+        riddle.set_rank_solutions(true);
+        let ranked_puzzle = Puzzle::solved_from_riddle(riddle.clone());
+
+        assert_ne!(puzzle, ranked_puzzle);
+        assert!(puzzle.is_equivalent(&ranked_puzzle));
+        // solution not set yet
+        assert!(!puzzle.is_equivalent(&Puzzle::new_from_riddle(riddle)));
+        // different number of matchstick movements
+        assert!(
+            !puzzle.is_equivalent(&Puzzle::solved_from_riddle(Riddle::new(
+                "6+4=4".parse().unwrap(),
+                2
+            )))
+        );
+    }
+
     #[test]
     fn set_new_riddle() {
         let riddle_equation = Equation::new_from_symbols(vec![
@@ -207,4 +567,166 @@ mod test {
         assert_eq!(1, puzzle.search_and_set_solution());
         assert_eq!(expected_solution, puzzle.wrapped_solution);
     }
+
+    #[test]
+    fn solve_riddle_from_strings() {
+        assert_eq!(
+            Ok(vec!["2+3=5".to_string(), "3+2=5".to_string()]),
+            solve_riddle_strings("3+3=5", 1)
+        );
+        assert_eq!(
+            Err(ParseError::UnknownCharacter('*')),
+            solve_riddle_strings("2*3=6", 1)
+        );
+    }
+
+    #[test]
+    fn group_solutions_by_move_pattern() {
+        let puzzle = Puzzle::solved_from_riddle(Riddle::new("5+3=6".parse().unwrap(), 1));
+        let solutions_by_move_pattern = puzzle.solutions_by_move_pattern();
+
+        // five changes to three within the symbol
+        assert_eq!(
+            vec![&"3+3=6".parse::<Equation>().unwrap()],
+            solutions_by_move_pattern[&MovePattern::WithinSymbols]
+        );
+        // the matchstick moves from plus to five changing to minus and nine
+        assert_eq!(
+            vec![&"9-3=6".parse::<Equation>().unwrap()],
+            solutions_by_move_pattern[&MovePattern::BetweenSymbols]
+        );
+
+        let unsolved_puzzle = Puzzle::new_from_riddle(Riddle::new("5+3=6".parse().unwrap(), 1));
+        assert!(unsolved_puzzle.solutions_by_move_pattern().is_empty());
+    }
+
+    #[test]
+    fn sort_unique_solutions() {
+        let mut puzzle = Puzzle::new_from_riddle(Riddle::new("3+3=5".parse().unwrap(), 1));
+        assert!(puzzle.unique_sorted_solutions().is_empty());
+
+        let solution_equations = vec![
+            Equation::new_from_symbols(vec![Symbol::Three, Symbol::Equal, Symbol::OneVar2]),
+            Equation::new_from_symbols(vec![Symbol::OneVar2, Symbol::Equal, Symbol::OneVar2]),
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar2]),
+        ];
+        puzzle.manually_set_solution(SolutionWrapper::new_manually_set_solution(
+            solution_equations,
+        ));
+
+        assert_eq!(
+            vec![
+                "1=1".parse::<Equation>().unwrap(),
+                "3=1".parse::<Equation>().unwrap()
+            ],
+            puzzle.unique_sorted_solutions()
+        );
+    }
+
+    #[test]
+    fn verify_manually_set_solution() {
+        let mut puzzle = Puzzle::new_from_riddle(Riddle::new("6+4=4".parse().unwrap(), 1));
+        assert_eq!(Ok(()), puzzle.verify_manual_solution());
+
+        let solution_equations: Vec<Equation> =
+            vec!["8-4=4".parse().unwrap(), "0+4=4".parse().unwrap()];
+        puzzle.manually_set_solution(SolutionWrapper::new_manually_set_solution(
+            solution_equations,
+        ));
+        assert_eq!(Ok(()), puzzle.verify_manual_solution());
+
+        // valid, but two matchsticks are moved, and a single symbol that can't be formed
+        let wrong_equations = vec![
+            "4+0=4".parse().unwrap(),
+            Equation::new_from_symbols(vec![Symbol::Three]),
+        ];
+        puzzle.manually_set_solution(SolutionWrapper::new_manually_set_solution(
+            wrong_equations.clone(),
+        ));
+        assert_eq!(Err(wrong_equations), puzzle.verify_manual_solution());
+    }
+
+    #[test]
+    fn same_signature_after_solving_again() {
+        let riddles = [("6+4=4", 1), ("9-5=4", 2), ("1=+", 2), ("3+3=5", 1)];
+
+        for (riddle_equation, number_matchstick_movements) in riddles {
+            let riddle = Riddle::new(
+                riddle_equation.parse().unwrap(),
+                number_matchstick_movements,
+            );
+            let puzzle = Puzzle::solved_from_riddle(riddle.clone());
+            let mut ranked_riddle = riddle;
+            ranked_riddle.set_rank_solutions(true);
+            let ranked_puzzle = Puzzle::solved_from_riddle(ranked_riddle);
+
+            let signature = |puzzle: &Puzzle| {
+                puzzle
+                    .wrapped_solution
+                    .get_inner_reference()
+                    .unwrap()
+                    .signature()
+            };
+            assert_eq!(signature(&puzzle), signature(&ranked_puzzle));
+        }
+    }
+
+    #[test]
+    fn draw_unsolved_puzzle() {
+        let puzzle = Puzzle {
+            riddle: Riddle::new(Equation::new_from_symbols(vec![Symbol::Seven]), 2),
+            wrapped_solution: SolutionWrapper::NotYetSet,
+        };
+
+        let expected_string = "Riddle (move 2 matchstick(s)):
+ ___ 
+    |
+    |
+    |
+    |
+
+Solution not set yet";
+        assert_eq!(expected_string, puzzle.to_string());
+    }
+
+    #[test]
+    fn count_distinct_solutions_with_variants() {
+        let puzzle = Puzzle {
+            riddle: Riddle::new(Equation::new_from_symbols(vec![Symbol::Seven]), 1),
+            wrapped_solution: SolutionWrapper::new_programmatically_set_solution(vec![
+                Equation::new_from_symbols(vec![Symbol::FourVar1, Symbol::Equal, Symbol::FourVar1]),
+                Equation::new_from_symbols(vec![Symbol::FourVar2, Symbol::Equal, Symbol::FourVar1]),
+                Equation::new_from_symbols(vec![Symbol::Nine, Symbol::Equal, Symbol::Nine]),
+            ]),
+        };
+
+        assert_eq!(2, puzzle.distinct_solution_count());
+    }
+
+    #[test]
+    fn count_no_distinct_solutions_if_unsolved() {
+        let puzzle = Puzzle {
+            riddle: Riddle::new(Equation::new_from_symbols(vec![Symbol::Seven]), 1),
+            wrapped_solution: SolutionWrapper::NotYetSet,
+        };
+
+        assert_eq!(0, puzzle.distinct_solution_count());
+    }
+
+    #[test]
+    fn solve_batch_like_individual_riddles() {
+        let riddles = vec![
+            Riddle::new("7-3=4".parse().unwrap(), 1),
+            Riddle::new("3+3=5".parse().unwrap(), 1),
+            Riddle::new("9-5=4".parse().unwrap(), 2),
+            Riddle::new("6+4=4".parse().unwrap(), 1),
+        ];
+
+        let expected_wrapped_solutions = riddles
+            .iter()
+            .map(|riddle| riddle.solve())
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected_wrapped_solutions, solve_batch(&riddles));
+    }
 }
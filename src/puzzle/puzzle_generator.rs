@@ -1,12 +1,29 @@
+use std::ops::{ControlFlow, RangeInclusive};
+use std::sync::OnceLock;
+
+use itertools::Itertools;
+
 use super::Puzzle;
 use super::Riddle;
-use crate::equation::EquationPattern;
+use super::solution::SolutionWrapper;
+use crate::equation::{Equation, EquationPattern, PatternError};
+use crate::symbol::{Symbol, SymbolFilter};
+use crate::transition::{TransitionKind, TransitionSequence};
 
 /// Searches for specific [`Puzzle`]s where [`Riddle`] (and solution) fulfill given general [`EquationPattern`]
 pub struct PuzzleGenerator {
     riddle_equation_pattern: EquationPattern,
     number_matchstick_movements: usize,
     solution_equation_pattern: Option<EquationPattern>,
+    allowed_operators: Option<Vec<Symbol>>,
+    count_distinct_solutions: bool,
+    min_riddle_matchsticks: usize,
+    required_operator_change: Option<(Symbol, Symbol)>,
+    require_different_operators: bool,
+    excluded_symbols: Vec<Symbol>,
+    operand_range: Option<RangeInclusive<i64>>,
+    // equations of the riddle pattern, derived once and reused while only other settings change
+    riddle_equations: OnceLock<Vec<Equation>>,
 }
 
 impl PuzzleGenerator {
@@ -29,48 +46,410 @@ impl PuzzleGenerator {
             riddle_equation_pattern,
             number_matchstick_movements,
             solution_equation_pattern: None,
+            allowed_operators: None,
+            count_distinct_solutions: false,
+            min_riddle_matchsticks: 0,
+            required_operator_change: None,
+            require_different_operators: false,
+            excluded_symbols: Vec::new(),
+            operand_range: None,
+            riddle_equations: OnceLock::new(),
         }
     }
 
+    /// Like [`PuzzleGenerator::new`] but the riddle [`EquationPattern`] is validated first
+    /// ```
+    /// # use matchstick::equation::{EquationPattern, PatternError};
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::symbol::SymbolFilter;
+    /// let equation_pattern = EquationPattern::new_from_symbol_filters(vec![SymbolFilter::List(vec![])]);
+    /// assert!(matches!(
+    ///     PuzzleGenerator::try_new(equation_pattern, 2),
+    ///     Err(PatternError::EmptyList(0))
+    /// ));
+    /// ```
+    pub fn try_new(
+        riddle_equation_pattern: EquationPattern,
+        number_matchstick_movements: usize,
+    ) -> Result<Self, PatternError> {
+        riddle_equation_pattern.validate()?;
+        Ok(Self::new(
+            riddle_equation_pattern,
+            number_matchstick_movements,
+        ))
+    }
+
     /// Find all [`Puzzle`]s where the riddle matches the given pattern and has only n solutions \
-    /// If the solution pattern is given, this only returns [`Puzzle`]s where the solution matches
+    /// If the solution pattern is given, this only returns [`Puzzle`]s where the solution matches. \
+    /// If distinct solutions are counted, solutions only differing in symbol variants count as one
+    /// (s. [`PuzzleGenerator::set_count_distinct_solutions`])
+    ///
+    /// With the feature `tracing` each run is a span reporting how many riddles are solved
+    /// and how many [`Puzzle`]s are found, forming equations is reported by nested spans
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub fn derive_puzzles_with_n_solutions(&self, number_solutions: usize) -> Vec<Puzzle> {
-        let mut puzzles = Vec::new();
+        let riddle_equations = self.riddle_candidates();
+        #[cfg(feature = "tracing")]
+        let number_riddle_equations = riddle_equations.len();
+
+        let puzzles = riddle_equations
+            .into_iter()
+            .filter_map(|riddle_equation| {
+                self.puzzle_with_n_solutions(riddle_equation, number_solutions)
+            })
+            .collect::<Vec<_>>();
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(
+            number_riddle_equations,
+            number_puzzles = puzzles.len(),
+            "derived puzzles"
+        );
+
+        puzzles
+    }
+
+    /// Like [`PuzzleGenerator::derive_puzzles_with_n_solutions`] but each [`Puzzle`] is handed
+    /// to the callback as soon as it is found, instead of collecting all of them \
+    /// Returning [`ControlFlow::Break`] from the callback stops the search,
+    /// so the remaining riddles aren't solved at all
+    /// ```
+    /// # use std::ops::ControlFlow;
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let puzzle_generator = PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+    ///
+    /// // stop after the first three puzzles
+    /// let mut puzzles = Vec::new();
+    /// puzzle_generator.for_each_puzzle(1, |puzzle| {
+    ///     puzzles.push(puzzle);
+    ///     match puzzles.len() {
+    ///         3 => ControlFlow::Break(()),
+    ///         _ => ControlFlow::Continue(()),
+    ///     }
+    /// });
+    /// assert_eq!(3, puzzles.len());
+    /// ```
+    pub fn for_each_puzzle(
+        &self,
+        number_solutions: usize,
+        mut f: impl FnMut(Puzzle) -> ControlFlow<()>,
+    ) {
+        for riddle_equation in self.riddle_candidates() {
+            if let Some(puzzle) = self.puzzle_with_n_solutions(riddle_equation, number_solutions)
+                && f(puzzle).is_break()
+            {
+                return;
+            }
+        }
+    }
+
+    /// Like [`PuzzleGenerator::derive_puzzles_with_n_solutions`] but the [`Puzzle`]s are sorted
+    /// by the text of their riddle equation, so the order doesn't depend on how riddles are formed \
+    /// Riddles with the same text, i.e. only differing in symbol variants,
+    /// are sorted by their [`Equation`] ordering
+    /// ```
+    /// # use matchstick::equation::{Equation, EquationPattern};
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let puzzle_generator = PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+    /// let puzzles = puzzle_generator.derive_puzzles_sorted(1);
+    ///
+    /// // "0 + 0 = 0" comes first although digits are formed in declaration order starting with "1"
+    /// assert_eq!(
+    ///     &"0+0=0".parse::<Equation>().unwrap(),
+    ///     puzzles[0].get_riddle().get_riddle_equation()
+    /// );
+    /// ```
+    pub fn derive_puzzles_sorted(&self, number_solutions: usize) -> Vec<Puzzle> {
+        let mut puzzles = self.derive_puzzles_with_n_solutions(number_solutions);
+        puzzles.sort_by_cached_key(|puzzle| {
+            let riddle_equation = puzzle.get_riddle().get_riddle_equation();
+            (riddle_equation.to_plain_text(), riddle_equation.clone())
+        });
+
+        puzzles
+    }
+
+    /// Picks one [`Puzzle`] that [`PuzzleGenerator::derive_puzzles_with_n_solutions`] would find \
+    /// The riddle candidates are visited in an order shuffled by the seed and the first matching
+    /// [`Puzzle`] is returned, so the same seed always yields the same [`Puzzle`]
+    /// (as long as the version of `rand` stays the same). \
+    /// Returns `None` if there is no matching [`Puzzle`]
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let puzzle_generator = PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+    ///
+    /// let puzzle = puzzle_generator.derive_random_puzzle(1, 42);
+    /// assert!(puzzle.is_some());
+    /// assert_eq!(puzzle, puzzle_generator.derive_random_puzzle(1, 42));
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn derive_random_puzzle(&self, number_solutions: usize, seed: u64) -> Option<Puzzle> {
+        use rand::SeedableRng;
+        use rand::seq::SliceRandom;
+
+        let mut riddle_equations = self.riddle_candidates();
+        riddle_equations.shuffle(&mut rand::rngs::StdRng::seed_from_u64(seed));
+
+        // solving is expensive, so only riddles up to the first match are solved
+        riddle_equations.into_iter().find_map(|riddle_equation| {
+            self.puzzle_with_n_solutions(riddle_equation, number_solutions)
+        })
+    }
+
+    /// Estimate of the work of a generation run, e.g. to warn before starting a long one \
+    /// This is the number of riddle equations described by the riddle [`EquationPattern`]
+    /// times the number of matchstick movement patterns examined when solving each of them.
+    /// Further riddle restrictions are not considered, so this is an upper limit.
+    /// Saturates at [`u128::MAX`]
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let puzzle_generator = PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+    ///
+    /// // 13 * 13 * 13 riddle equations with 5 * 5 movement patterns each
+    /// assert_eq!(2197 * 25, puzzle_generator.estimated_work());
+    /// ```
+    pub fn estimated_work(&self) -> u128 {
+        let number_movement_patterns = TransitionSequence::count_move_n(
+            self.number_matchstick_movements,
+            self.riddle_equation_pattern.get_symbol_filters().len(),
+        )
+        .map_or(u128::MAX, |number_movement_patterns| {
+            number_movement_patterns as u128
+        });
+
+        self.riddle_equation_pattern
+            .count_concrete_equations()
+            .saturating_mul(number_movement_patterns)
+    }
+
+    // all equations matching the riddle pattern and the further riddle restrictions
+    fn riddle_candidates(&self) -> Vec<Equation> {
+        self.riddle_equations
+            .get_or_init(|| self.riddle_equation_pattern.derive_concrete_equations())
+            .iter()
+            .filter(|riddle_equation| self.fulfills_riddle_restrictions(riddle_equation))
+            .cloned()
+            .collect()
+    }
+
+    // restrictions of the riddle equation beyond the riddle pattern
+    fn fulfills_riddle_restrictions(&self, riddle_equation: &Equation) -> bool {
+        self.uses_allowed_operators(riddle_equation)
+            && riddle_equation.matchstick_count() >= self.min_riddle_matchsticks
+            && self.avoids_excluded_symbols(riddle_equation)
+            && self.has_operands_in_range(riddle_equation)
+    }
+
+    fn has_operands_in_range(&self, equation: &Equation) -> bool {
+        let Some(operand_range) = &self.operand_range else {
+            return true;
+        };
+
+        equation
+            .numbers()
+            .is_some_and(|numbers| numbers.iter().all(|number| operand_range.contains(number)))
+    }
+
+    fn avoids_excluded_symbols(&self, equation: &Equation) -> bool {
+        !equation
+            .get_symbols()
+            .iter()
+            .any(|symbol| self.excluded_symbols.contains(symbol))
+    }
+
+    // solves the riddle, solution equations with excluded symbols are dropped
+    fn solve(&self, riddle_equation: Equation) -> Puzzle {
+        let mut puzzle = Puzzle::solved_from_riddle(Riddle::new(
+            riddle_equation,
+            self.number_matchstick_movements,
+        ));
+
+        if !self.excluded_symbols.is_empty()
+            && let Ok(solution) = puzzle.wrapped_solution.get_inner_reference()
+        {
+            let solution_equations = solution
+                .get_solution_equations()
+                .iter()
+                .filter(|solution_equation| self.avoids_excluded_symbols(solution_equation))
+                .cloned()
+                .collect();
+            puzzle.wrapped_solution =
+                SolutionWrapper::new_programmatically_set_solution(solution_equations);
+        }
 
-        'outer: for riddle_equation in self.riddle_equation_pattern.derive_concrete_equations() {
-            // for each starting equation a new puzzle is set up to be solved then
-            let mut puzzle = Puzzle::new_from_riddle(Riddle::new(
-                riddle_equation,
-                self.number_matchstick_movements,
-            ));
+        puzzle
+    }
+
+    // solves the riddle and returns the puzzle only if it has the requested number of solutions
+    // and all solutions fulfill the solution pattern if set
+    fn puzzle_with_n_solutions(
+        &self,
+        riddle_equation: Equation,
+        number_solutions: usize,
+    ) -> Option<Puzzle> {
+        let puzzle = self.solve(riddle_equation);
 
-            if number_solutions != puzzle.search_and_set_solution() {
-                continue; // the riddle_equation has not requested number of solutions
+        let number_found_solutions = match self.count_distinct_solutions {
+            true => puzzle.distinct_solution_count(),
+            false => puzzle
+                .wrapped_solution
+                .get_inner_reference()
+                .map_or(0, |solution| solution.get_solution_equations().len()),
+        };
+        if number_solutions != number_found_solutions {
+            return None; // the riddle_equation has not requested number of solutions
+        }
+
+        // if solution pattern is set, all solution equations must fulfill it
+        // in order for the riddle equation to be valid
+        if let Some(solution_equation_pattern) = &self.solution_equation_pattern {
+            let solution = puzzle.wrapped_solution.get_inner_reference().ok()?;
+            if !solution
+                .get_solution_equations()
+                .iter()
+                .all(|solution_equation| {
+                    solution_equation.matches_pattern(solution_equation_pattern)
+                })
+            {
+                return None;
             }
+        }
 
-            // if solution pattern is set, all solution equations must fulfill it
-            // in order for the riddle equation to be valid
-            if let Some(solution_equation_pattern) = &self.solution_equation_pattern {
-                let solution = match puzzle.wrapped_solution.get_inner_reference() {
-                    Ok(solution_equations) => solution_equations,
-                    Err(()) => continue 'outer, // go to next riddle equation,
-                };
-                for solution_equation in solution.get_solution_equations() {
-                    if !solution_equation.fulfills_abstract_equation(solution_equation_pattern) {
-                        continue 'outer; // go to next riddle equation
-                    }
-                }
+        // if an operator change is required, at least one solution must perform it
+        if let Some((riddle_operator, solution_operator)) = &self.required_operator_change {
+            let solution = puzzle.wrapped_solution.get_inner_reference().ok()?;
+            let riddle_equation = puzzle.riddle.get_riddle_equation();
+            let changes_operator =
+                solution
+                    .get_solution_equations()
+                    .iter()
+                    .any(|solution_equation| {
+                        // equations of different length aren't aligned, so they change no operator
+                        riddle_equation
+                            .transition_to(solution_equation)
+                            .is_some_and(|transition_sequence| {
+                                riddle_equation
+                                    .get_symbols()
+                                    .iter()
+                                    .zip(solution_equation.get_symbols())
+                                    .zip(&transition_sequence.transitions)
+                                    .any(|((riddle_symbol, solution_symbol), transition)| {
+                                        transition.kind() != TransitionKind::NoChange
+                                            && riddle_symbol == riddle_operator
+                                            && solution_symbol == solution_operator
+                                    })
+                            })
+                    });
+            if !changes_operator {
+                return None;
             }
+        }
 
-            puzzles.push(puzzle);
+        // if different operators are required, at least one solution must use other operators
+        if self.require_different_operators {
+            let solution = puzzle.wrapped_solution.get_inner_reference().ok()?;
+            let riddle_operators = puzzle
+                .riddle
+                .get_riddle_equation()
+                .operators()
+                .into_iter()
+                .sorted()
+                .collect_vec();
+            if !solution
+                .get_solution_equations()
+                .iter()
+                .any(|solution_equation| {
+                    solution_equation
+                        .operators()
+                        .into_iter()
+                        .sorted()
+                        .collect_vec()
+                        != riddle_operators
+                })
+            {
+                return None;
+            }
+        }
+
+        Some(puzzle)
+    }
+
+    /// Find all [`Puzzle`]s whose solutions contain the given target [`Equation`] \
+    /// Moving matchsticks is reversible, so the riddle candidates are formed by moving
+    /// matchsticks of the target equation. They still need to fulfill the riddle [`EquationPattern`]
+    /// ```
+    /// # use matchstick::equation::{Equation, EquationPattern};
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let riddle_equation_pattern: EquationPattern = "NON=N".parse().unwrap();
+    /// let target_solution_equation: Equation = "3+2=5".parse().unwrap();
+    ///
+    /// let puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 1);
+    /// let puzzles = puzzle_generator.derive_puzzles_for_target_solution(&target_solution_equation);
+    ///
+    /// let riddle_equation: Equation = "3+3=5".parse().unwrap();
+    /// assert!(puzzles
+    ///     .iter()
+    ///     .any(|puzzle| puzzle.get_riddle().get_riddle_equation() == &riddle_equation));
+    /// ```
+    pub fn derive_puzzles_for_target_solution(
+        &self,
+        target_solution_equation: &Equation,
+    ) -> Vec<Puzzle> {
+        let mut puzzles = Vec::new();
+
+        let riddle_equations = target_solution_equation
+            .move_n_matchsticks(self.number_matchstick_movements)
+            .into_iter()
+            .unique()
+            .filter(|riddle_equation| {
+                riddle_equation.matches_pattern(&self.riddle_equation_pattern)
+                    && self.fulfills_riddle_restrictions(riddle_equation)
+            });
+
+        for riddle_equation in riddle_equations {
+            let puzzle = self.solve(riddle_equation);
+
+            // the target might not be part of the solutions, e.g. if the riddle is already valid
+            let contains_target = match puzzle.wrapped_solution.get_inner_reference() {
+                Ok(solution) => solution
+                    .get_solution_equations()
+                    .contains(target_solution_equation),
+                Err(()) => false,
+            };
+            if contains_target {
+                puzzles.push(puzzle);
+            }
         }
 
         puzzles
     }
 
+    // Operators at positions with an explicit [`SymbolFilter::List`] are always allowed
+    // Everywhere else only the allowed operators may appear, if they are set
+    fn uses_allowed_operators(&self, riddle_equation: &Equation) -> bool {
+        let Some(allowed_operators) = &self.allowed_operators else {
+            return true;
+        };
+
+        riddle_equation
+            .get_symbols()
+            .iter()
+            .zip(self.riddle_equation_pattern.get_symbol_filters())
+            .all(|(symbol, symbol_filter)| {
+                symbol.is_number()
+                    || matches!(symbol_filter, SymbolFilter::List(_))
+                    || allowed_operators.contains(symbol)
+            })
+    }
+
     /// Setter function for number of matchstick movements
     pub fn set_riddle_equation_pattern(&mut self, riddle_equation_pattern: EquationPattern) {
         self.riddle_equation_pattern = riddle_equation_pattern;
+        // equations of the previous pattern are outdated
+        self.riddle_equations = OnceLock::new();
     }
 
     /// Setter function for number of matchstick movements
@@ -98,6 +477,181 @@ impl PuzzleGenerator {
         self.solution_equation_pattern = Some(solution_equation_pattern);
     }
 
+    /// Restrict which operators may appear in the riddle equations \
+    /// This applies to all positions except those with an explicit [`SymbolFilter::List`],
+    /// so a designated equal sign is kept
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::symbol::Symbol;
+    /// let riddle_equation_pattern: EquationPattern = "NON=N".parse().unwrap();
+    /// let mut puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 1);
+    /// puzzle_generator.set_allowed_operators(vec![Symbol::Plus]);
+    ///
+    /// for puzzle in puzzle_generator.derive_puzzles_with_n_solutions(1) {
+    ///     let riddle_symbols = puzzle.get_riddle().get_riddle_equation().get_symbols();
+    ///     assert_eq!(Symbol::Plus, riddle_symbols[1]);
+    /// }
+    /// ```
+    pub fn set_allowed_operators(&mut self, allowed_operators: Vec<Symbol>) {
+        self.allowed_operators = Some(allowed_operators);
+    }
+
+    /// Set whether the number of solutions only counts distinct solutions
+    /// (s. [`Puzzle::distinct_solution_count`]) \
+    /// By default every solution equation is counted, including variant duplicates
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::symbol::{Symbol, SymbolFilter};
+    /// // "1 = 1" is found twice from "1 = +" using both variants of the second one
+    /// let riddle_equation_pattern = EquationPattern::new_from_symbol_filters(vec![
+    ///     SymbolFilter::List(vec![Symbol::OneVar1]),
+    ///     SymbolFilter::List(vec![Symbol::Equal]),
+    ///     SymbolFilter::List(vec![Symbol::Plus]),
+    /// ]);
+    /// let mut puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 2);
+    /// assert!(puzzle_generator.derive_puzzles_with_n_solutions(1).is_empty());
+    ///
+    /// puzzle_generator.set_count_distinct_solutions(true);
+    /// assert_eq!(1, puzzle_generator.derive_puzzles_with_n_solutions(1).len());
+    /// ```
+    pub fn set_count_distinct_solutions(&mut self, count_distinct_solutions: bool) {
+        self.count_distinct_solutions = count_distinct_solutions;
+    }
+
+    /// Set how many matchsticks a riddle equation needs at least
+    /// (s. [`Equation::matchstick_count`]) \
+    /// Sparser riddle equations are discarded before solving them
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let riddle_equation_pattern: EquationPattern = "NON=N".parse().unwrap();
+    /// let mut puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 1);
+    /// puzzle_generator.set_min_riddle_matchsticks(20);
+    ///
+    /// for puzzle in puzzle_generator.derive_puzzles_with_n_solutions(1) {
+    ///     assert!(puzzle.get_riddle().get_riddle_equation().matchstick_count() >= 20);
+    /// }
+    /// ```
+    pub fn set_min_riddle_matchsticks(&mut self, min_riddle_matchsticks: usize) {
+        self.min_riddle_matchsticks = min_riddle_matchsticks;
+    }
+
+    /// Keep only [`Puzzle`]s where at least one solution turns the first operator
+    /// of the riddle equation into the second one at the same position,
+    /// e.g. a plus into a minus
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// # use matchstick::symbol::Symbol;
+    /// # use matchstick::transition::TransitionKind;
+    /// let riddle_equation_pattern: EquationPattern = "N+N=N".parse().unwrap();
+    /// let mut puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 1);
+    /// puzzle_generator.set_required_operator_change(Symbol::Plus, Symbol::Minus);
+    ///
+    /// for puzzle in puzzle_generator.derive_puzzles_with_n_solutions(1) {
+    ///     let SolutionWrapper::ProgrammaticallySet(solution) = puzzle.get_wrapped_solution() else {
+    ///         panic!("solution should be set");
+    ///     };
+    ///     let riddle_equation = puzzle.get_riddle().get_riddle_equation();
+    ///     let solution_equation = &solution.get_solution_equations()[0];
+    ///     let transition_sequence = riddle_equation.transition_to(solution_equation).unwrap();
+    ///
+    ///     // the changed symbols include a plus turned into a minus
+    ///     assert!(
+    ///         transition_sequence
+    ///             .transitions
+    ///             .iter()
+    ///             .zip(riddle_equation.get_symbols().iter().zip(solution_equation.get_symbols()))
+    ///             .any(|(transition, symbols)| {
+    ///                 transition.kind() != TransitionKind::NoChange
+    ///                     && symbols == (&Symbol::Plus, &Symbol::Minus)
+    ///             })
+    ///     );
+    /// }
+    /// ```
+    pub fn set_required_operator_change(
+        &mut self,
+        riddle_operator: Symbol,
+        solution_operator: Symbol,
+    ) {
+        self.required_operator_change = Some((riddle_operator, solution_operator));
+    }
+
+    /// Keep only [`Puzzle`]s where at least one solution uses other operators than
+    /// the riddle equation (s. [`Equation::operators`]) \
+    /// The operators are compared regardless of their order, so moving a plus
+    /// from one place to another doesn't count as a change. Unlike
+    /// [`PuzzleGenerator::set_required_operator_change`] any change of operators is accepted
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// let riddle_equation_pattern: EquationPattern = "NON=N".parse().unwrap();
+    /// let mut puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 1);
+    /// puzzle_generator.set_require_different_operators(true);
+    ///
+    /// for puzzle in puzzle_generator.derive_puzzles_with_n_solutions(1) {
+    ///     let SolutionWrapper::ProgrammaticallySet(solution) = puzzle.get_wrapped_solution() else {
+    ///         panic!("solution should be set");
+    ///     };
+    ///     assert_ne!(
+    ///         puzzle.get_riddle().get_riddle_equation().operators(),
+    ///         solution.get_solution_equations()[0].operators()
+    ///     );
+    /// }
+    /// ```
+    pub fn set_require_different_operators(&mut self, require_different_operators: bool) {
+        self.require_different_operators = require_different_operators;
+    }
+
+    /// Exclude symbols from riddle and solution equations, e.g. alternative digit variants
+    /// for a consistent rendering \
+    /// Riddle equations with excluded symbols are skipped and solution equations with
+    /// excluded symbols are dropped before the solutions are counted
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// # use matchstick::symbol::Symbol;
+    /// // "1 = +" becomes "1 = 1" with either variant of one
+    /// let mut puzzle_generator = PuzzleGenerator::new("1=+".parse::<EquationPattern>().unwrap(), 2);
+    /// assert!(puzzle_generator.derive_puzzles_with_n_solutions(1).is_empty());
+    ///
+    /// puzzle_generator.set_excluded_symbols(vec![
+    ///     Symbol::OneVar2,
+    ///     Symbol::FourVar2,
+    ///     Symbol::EightVar2,
+    /// ]);
+    /// assert_eq!(1, puzzle_generator.derive_puzzles_with_n_solutions(1).len());
+    /// ```
+    pub fn set_excluded_symbols(&mut self, excluded_symbols: Vec<Symbol>) {
+        self.excluded_symbols = excluded_symbols;
+    }
+
+    /// Set the range all numbers of a riddle equation must lie within,
+    /// i.e. its operands and results, e.g. for puzzles suited to young children \
+    /// A negation belongs to its number, so "-3" is an operand below zero
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::puzzle::puzzle_generator::PuzzleGenerator;
+    /// let mut puzzle_generator = PuzzleGenerator::new("9+N=N".parse::<EquationPattern>().unwrap(), 1);
+    /// assert!(!puzzle_generator.derive_puzzles_with_n_solutions(1).is_empty());
+    ///
+    /// // the nine of every riddle is out of range
+    /// puzzle_generator.set_operand_range(0..=5);
+    /// assert!(puzzle_generator.derive_puzzles_with_n_solutions(1).is_empty());
+    /// ```
+    pub fn set_operand_range(&mut self, operand_range: RangeInclusive<i64>) {
+        self.operand_range = Some(operand_range);
+    }
+
+    /// Getter function for the range of riddle numbers
+    pub fn get_operand_range(&self) -> &Option<RangeInclusive<i64>> {
+        &self.operand_range
+    }
+
     /// Getter function for riddle [`EquationPattern`]
     pub fn get_riddle_equation_pattern(&self) -> &EquationPattern {
         &self.riddle_equation_pattern
@@ -112,10 +666,42 @@ impl PuzzleGenerator {
     pub fn get_solution_equation_pattern(&self) -> &Option<EquationPattern> {
         &self.solution_equation_pattern
     }
+
+    /// Getter function for allowed operators
+    pub fn get_allowed_operators(&self) -> &Option<Vec<Symbol>> {
+        &self.allowed_operators
+    }
+
+    /// Getter function for whether only distinct solutions are counted
+    pub fn get_count_distinct_solutions(&self) -> &bool {
+        &self.count_distinct_solutions
+    }
+
+    /// Getter function for minimum number of matchsticks in riddle equations
+    pub fn get_min_riddle_matchsticks(&self) -> &usize {
+        &self.min_riddle_matchsticks
+    }
+
+    /// Getter function for symbols excluded from riddle and solution equations
+    pub fn get_excluded_symbols(&self) -> &Vec<Symbol> {
+        &self.excluded_symbols
+    }
+
+    /// Getter function for the operator change a solution must perform
+    pub fn get_required_operator_change(&self) -> &Option<(Symbol, Symbol)> {
+        &self.required_operator_change
+    }
+
+    /// Getter function for whether a solution must use other operators than the riddle
+    pub fn get_require_different_operators(&self) -> &bool {
+        &self.require_different_operators
+    }
 }
 
 #[cfg(test)]
 mod test {
+    use std::iter;
+
     use super::*;
     use crate::equation::Equation;
     use crate::puzzle::SolutionWrapper;
@@ -133,6 +719,14 @@ mod test {
             riddle_equation_pattern: riddle_pattern,
             solution_equation_pattern: None,
             number_matchstick_movements: 0,
+            allowed_operators: None,
+            count_distinct_solutions: false,
+            min_riddle_matchsticks: 0,
+            required_operator_change: None,
+            require_different_operators: false,
+            excluded_symbols: Vec::new(),
+            operand_range: None,
+            riddle_equations: OnceLock::new(),
         };
 
         let expected_puzzles = vec![Puzzle {
@@ -163,6 +757,14 @@ mod test {
             riddle_equation_pattern: riddle_pattern,
             solution_equation_pattern: None,
             number_matchstick_movements: 1,
+            allowed_operators: None,
+            count_distinct_solutions: false,
+            min_riddle_matchsticks: 0,
+            required_operator_change: None,
+            require_different_operators: false,
+            excluded_symbols: Vec::new(),
+            operand_range: None,
+            riddle_equations: OnceLock::new(),
         };
 
         assert_eq!(
@@ -178,6 +780,14 @@ mod test {
             riddle_equation_pattern: equation_pattern,
             number_matchstick_movements: 3,
             solution_equation_pattern: None,
+            allowed_operators: None,
+            count_distinct_solutions: false,
+            min_riddle_matchsticks: 0,
+            required_operator_change: None,
+            require_different_operators: false,
+            excluded_symbols: Vec::new(),
+            operand_range: None,
+            riddle_equations: OnceLock::new(),
         };
 
         assert_eq!(3, *puzzle_generator.get_number_matchstick_movements());
@@ -193,6 +803,14 @@ mod test {
             riddle_equation_pattern: equation_pattern.clone(),
             number_matchstick_movements: 3,
             solution_equation_pattern: None,
+            allowed_operators: None,
+            count_distinct_solutions: false,
+            min_riddle_matchsticks: 0,
+            required_operator_change: None,
+            require_different_operators: false,
+            excluded_symbols: Vec::new(),
+            operand_range: None,
+            riddle_equations: OnceLock::new(),
         };
 
         assert_eq!(
@@ -209,4 +827,252 @@ mod test {
             *puzzle_generator.get_riddle_equation_pattern()
         );
     }
+
+    #[test]
+    fn generate_puzzles_for_target_solution() {
+        let riddle_pattern = EquationPattern::new_from_symbol_filters(vec![SymbolFilter::IsAny; 5]);
+        let puzzle_generator = PuzzleGenerator::new(riddle_pattern, 1);
+        let target_solution_equation: Equation = "3+2=5".parse().unwrap();
+
+        let puzzles =
+            puzzle_generator.derive_puzzles_for_target_solution(&target_solution_equation);
+
+        let riddle_equation: Equation = "3+3=5".parse().unwrap();
+        assert!(
+            puzzles
+                .iter()
+                .any(|puzzle| puzzle.riddle.get_riddle_equation() == &riddle_equation)
+        );
+        for puzzle in puzzles {
+            let solution = puzzle.wrapped_solution.get_inner_reference().unwrap();
+            assert!(
+                solution
+                    .get_solution_equations()
+                    .contains(&target_solution_equation)
+            );
+        }
+    }
+
+    #[test]
+    fn generate_puzzles_with_allowed_operators() {
+        let riddle_pattern = EquationPattern::new_from_symbol_filters(vec![
+            SymbolFilter::IsNumber,
+            SymbolFilter::IsOperator,
+            SymbolFilter::IsNumber,
+            SymbolFilter::List(vec![Symbol::Equal]),
+            SymbolFilter::IsNumber,
+        ]);
+        let mut puzzle_generator = PuzzleGenerator::new(riddle_pattern, 1);
+        puzzle_generator.set_allowed_operators(vec![Symbol::Minus]);
+
+        let puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        assert!(!puzzles.is_empty());
+        for puzzle in puzzles {
+            let riddle_symbols = puzzle.riddle.get_riddle_equation().get_symbols();
+            assert_eq!(Symbol::Minus, riddle_symbols[1]);
+            assert_eq!(Symbol::Equal, riddle_symbols[3]);
+        }
+    }
+
+    #[test]
+    fn discard_sparse_riddles() {
+        let riddle_pattern = EquationPattern::new_from_symbol_filters(vec![
+            SymbolFilter::IsNumber,
+            SymbolFilter::List(vec![Symbol::Equal]),
+            SymbolFilter::List(vec![Symbol::Three]),
+        ]);
+        let mut puzzle_generator = PuzzleGenerator::new(riddle_pattern, 0);
+
+        // "3 = 3" consists of 12 matchsticks
+        puzzle_generator.set_min_riddle_matchsticks(12);
+        assert_eq!(1, puzzle_generator.derive_puzzles_with_n_solutions(1).len());
+
+        puzzle_generator.set_min_riddle_matchsticks(13);
+        assert!(
+            puzzle_generator
+                .derive_puzzles_with_n_solutions(1)
+                .is_empty()
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn pick_random_puzzle_among_derived_puzzles() {
+        let puzzle_generator = PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+        let puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        for seed in 0..5 {
+            let puzzle = puzzle_generator.derive_random_puzzle(1, seed).unwrap();
+            assert!(puzzles.contains(&puzzle));
+            assert_eq!(Some(puzzle), puzzle_generator.derive_random_puzzle(1, seed));
+        }
+        assert_eq!(None, puzzle_generator.derive_random_puzzle(1000, 0));
+    }
+
+    #[test]
+    fn sort_derived_puzzles_by_riddle_text() {
+        let puzzle_generator = PuzzleGenerator::new("N-N=N".parse::<EquationPattern>().unwrap(), 1);
+        let puzzles = puzzle_generator.derive_puzzles_sorted(1);
+
+        assert_eq!(
+            puzzle_generator.derive_puzzles_with_n_solutions(1).len(),
+            puzzles.len()
+        );
+        assert!(puzzles.is_sorted_by_key(|puzzle| {
+            let riddle_equation = puzzle.get_riddle().get_riddle_equation();
+            (riddle_equation.to_plain_text(), riddle_equation.clone())
+        }));
+    }
+
+    #[test]
+    fn keep_puzzles_changing_plus_into_minus() {
+        let mut puzzle_generator =
+            PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+        let all_puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        puzzle_generator.set_required_operator_change(Symbol::Plus, Symbol::Minus);
+        let puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        assert!(!puzzles.is_empty());
+        assert!(puzzles.len() < all_puzzles.len());
+        for puzzle in puzzles {
+            let solution = puzzle.wrapped_solution.get_inner_reference().unwrap();
+            assert_eq!(
+                Symbol::Minus,
+                solution.get_solution_equations()[0].get_symbols()[1]
+            );
+        }
+    }
+
+    #[test]
+    fn keep_puzzles_using_different_operators() {
+        let mut puzzle_generator =
+            PuzzleGenerator::new("NON=N".parse::<EquationPattern>().unwrap(), 1);
+        let all_puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        puzzle_generator.set_require_different_operators(true);
+        let puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        assert!(!puzzles.is_empty());
+        assert!(puzzles.len() < all_puzzles.len());
+        for puzzle in puzzles {
+            let solution = puzzle.wrapped_solution.get_inner_reference().unwrap();
+            assert_ne!(
+                puzzle.riddle.get_riddle_equation().operators(),
+                solution.get_solution_equations()[0].operators()
+            );
+        }
+    }
+
+    #[test]
+    fn share_generator_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PuzzleGenerator>();
+    }
+
+    #[test]
+    fn reuse_riddle_equations_until_pattern_changes() {
+        let mut puzzle_generator =
+            PuzzleGenerator::new("N+N=N".parse::<EquationPattern>().unwrap(), 1);
+        puzzle_generator.derive_puzzles_with_n_solutions(1);
+        assert_eq!(
+            Some(
+                &puzzle_generator
+                    .riddle_equation_pattern
+                    .derive_concrete_equations()
+            ),
+            puzzle_generator.riddle_equations.get()
+        );
+
+        puzzle_generator.set_number_matchstick_movements(2);
+        assert!(puzzle_generator.riddle_equations.get().is_some());
+
+        puzzle_generator.set_riddle_equation_pattern("N=N".parse().unwrap());
+        assert!(puzzle_generator.riddle_equations.get().is_none());
+        assert_eq!(
+            puzzle_generator
+                .riddle_equation_pattern
+                .derive_concrete_equations(),
+            puzzle_generator.riddle_candidates()
+        );
+    }
+
+    #[test]
+    fn exclude_alternative_variants() {
+        let mut puzzle_generator =
+            PuzzleGenerator::new("NON=N".parse::<EquationPattern>().unwrap(), 1);
+        let alternative_variants = vec![Symbol::OneVar2, Symbol::FourVar2, Symbol::EightVar2];
+        puzzle_generator.set_excluded_symbols(alternative_variants.clone());
+        let puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        assert!(!puzzles.is_empty());
+        for puzzle in puzzles {
+            let solution = puzzle.wrapped_solution.get_inner_reference().unwrap();
+            let equations = iter::once(puzzle.get_riddle().get_riddle_equation())
+                .chain(solution.get_solution_equations());
+            for equation in equations {
+                assert!(
+                    equation
+                        .get_symbols()
+                        .iter()
+                        .all(|symbol| !alternative_variants.contains(symbol))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hand_over_puzzles_like_derived() {
+        let puzzle_generator = PuzzleGenerator::new("N-N=N".parse().unwrap(), 1);
+
+        let mut puzzles = Vec::new();
+        puzzle_generator.for_each_puzzle(1, |puzzle| {
+            puzzles.push(puzzle);
+            ControlFlow::Continue(())
+        });
+        assert_eq!(puzzle_generator.derive_puzzles_with_n_solutions(1), puzzles);
+
+        let mut number_handed_over = 0;
+        puzzle_generator.for_each_puzzle(1, |_| {
+            number_handed_over += 1;
+            ControlFlow::Break(())
+        });
+        assert_eq!(1, number_handed_over);
+    }
+
+    #[test]
+    fn keep_riddles_with_operands_in_range() {
+        let mut puzzle_generator = PuzzleGenerator::new("N-N=N".parse().unwrap(), 1);
+        let all_puzzles = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        puzzle_generator.set_operand_range(2..=6);
+        let puzzles_in_range = puzzle_generator.derive_puzzles_with_n_solutions(1);
+
+        assert!(!puzzles_in_range.is_empty());
+        assert_eq!(
+            all_puzzles
+                .into_iter()
+                .filter(|puzzle| {
+                    puzzle
+                        .get_riddle()
+                        .get_riddle_equation()
+                        .numbers()
+                        .unwrap()
+                        .iter()
+                        .all(|number| (2..=6).contains(number))
+                })
+                .collect::<Vec<_>>(),
+            puzzles_in_range
+        );
+    }
+
+    #[test]
+    fn saturate_estimated_work() {
+        let riddle_equation_pattern =
+            EquationPattern::new_from_symbol_filters(vec![SymbolFilter::IsAny; 40]);
+        let puzzle_generator = PuzzleGenerator::new(riddle_equation_pattern, 2);
+
+        assert_eq!(u128::MAX, puzzle_generator.estimated_work());
+    }
 }
@@ -1,11 +1,114 @@
+use std::collections::HashSet;
+use std::io::BufRead;
+
+use itertools::Itertools;
+
 use super::SolutionWrapper;
-use crate::equation::Equation;
+use super::solution::DetailedSolution;
+use crate::equation::parse::ParseError;
+use crate::equation::validation::{ValidationCache, ValidationOptions};
+use crate::equation::{Equation, EquationPattern};
+use crate::symbol::Symbol;
+use crate::transition::{TransitionSequence, TransitionSequenceCache};
+
+/// Describes which digit variants (e.g. [`crate::symbol::Symbol::FourVar1`] and
+/// [`crate::symbol::Symbol::FourVar2`]) are kept when several solution equations
+/// only differ in their variants
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum VariantPolicy {
+    /// Keep every solution equation
+    #[default]
+    All,
+    /// Keep the solution equation using the most first variants (`Var1`)
+    PreferVar1,
+    /// Keep the solution equation requiring the fewest segment changes from the riddle equation
+    PreferClosest,
+}
+
+impl VariantPolicy {
+    // Reduces solution equations with the same mathematical value to one representative
+    // The first found solution equation is kept when the policy can't decide
+    fn select(
+        &self,
+        riddle_equation: &Equation,
+        solution_equations: Vec<Equation>,
+    ) -> Vec<Equation> {
+        if *self == VariantPolicy::All {
+            return solution_equations;
+        }
+
+        let mut selected_equations: Vec<Equation> = Vec::new();
+        for equation in solution_equations {
+            let representative = selected_equations
+                .iter_mut()
+                .find(|selected| selected.to_plain_text() == equation.to_plain_text());
+            match representative {
+                None => selected_equations.push(equation),
+                Some(representative) => {
+                    if self.prefers(&equation, representative, riddle_equation) {
+                        *representative = equation;
+                    }
+                }
+            }
+        }
+
+        selected_equations
+    }
+
+    // true if candidate is strictly better than the current representative
+    fn prefers(
+        &self,
+        candidate: &Equation,
+        current: &Equation,
+        riddle_equation: &Equation,
+    ) -> bool {
+        match self {
+            VariantPolicy::All => false,
+            VariantPolicy::PreferVar1 => {
+                candidate.count_alternative_variants() < current.count_alternative_variants()
+            }
+            VariantPolicy::PreferClosest => {
+                riddle_equation.segment_delta_to(candidate)
+                    < riddle_equation.segment_delta_to(current)
+            }
+        }
+    }
+}
+
+/// Describes what a solution equation of a [`Riddle`] must achieve
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum PuzzleGoal {
+    /// Form a true equation where all sides have the same value
+    #[default]
+    TrueEquation,
+    /// Form an expression without equal sign that has the largest possible value
+    Maximize,
+    /// Form an expression without equal sign that has the given value
+    ReachTarget(i64),
+}
+
+/// Reasons why solving a [`Riddle`] is aborted
+#[derive(Clone, Debug, PartialEq)]
+pub enum SolveError {
+    /// More candidates than allowed would need to be examined
+    TooManyCandidates,
+}
 
 /// Holds information to describe a matchstick riddle
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Riddle {
     riddle_equation: Equation,
     number_matchstick_movements: usize,
+    variant_policy: VariantPolicy,
+    validation_options: ValidationOptions,
+    rank_solutions: bool,
+    goal: PuzzleGoal,
+    up_to_n_movements: bool,
+    preserve_length: bool,
+    locked_positions: Vec<usize>,
+    collapse_commutative: bool,
+    fix_equals_position: bool,
+    solution_equation_pattern: Option<EquationPattern>,
 }
 
 impl Riddle {
@@ -28,22 +131,1112 @@ impl Riddle {
         Riddle {
             riddle_equation: equation,
             number_matchstick_movements,
+            variant_policy: VariantPolicy::default(),
+            validation_options: ValidationOptions::default(),
+            rank_solutions: false,
+            goal: PuzzleGoal::default(),
+            up_to_n_movements: false,
+            preserve_length: true,
+            locked_positions: Vec::new(),
+            collapse_commutative: false,
+            fix_equals_position: false,
+            solution_equation_pattern: None,
+        }
+    }
+
+    /// Reads a [`Riddle`] from a line holding the equation and the number of matchstick movements,
+    /// separated by whitespace, like "2-7=3 1" \
+    /// The equation must not contain whitespace itself, otherwise a line without
+    /// move count like "2 - 7 = 3" could be read as "2-7=" with 3 movements
+    /// ```
+    /// # use matchstick::equation::parse::ParseError;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// assert_eq!(
+    ///     Ok(Riddle::new("2-7=3".parse().unwrap(), 1)),
+    ///     Riddle::parse_line("2-7=3 1")
+    /// );
+    /// assert_eq!(Err(ParseError::MissingMoveCount), Riddle::parse_line("2-7=3"));
+    /// assert_eq!(
+    ///     Err(ParseError::InvalidRiddleLine("2 - 7 = 3 1".to_string())),
+    ///     Riddle::parse_line("2 - 7 = 3 1")
+    /// );
+    /// ```
+    pub fn parse_line(line: &str) -> Result<Self, ParseError> {
+        // exactly two words, the equation and the move count
+        let words = line.split_whitespace().collect_vec();
+        let [equation, move_count] = words[..] else {
+            return match words.len() {
+                0 | 1 => Err(ParseError::MissingMoveCount),
+                _ => Err(ParseError::InvalidRiddleLine(line.trim().to_string())),
+            };
+        };
+        let number_matchstick_movements = move_count
+            .parse()
+            .map_err(|_| ParseError::InvalidMoveCount(move_count.to_string()))?;
+
+        Ok(Riddle::new(equation.parse()?, number_matchstick_movements))
+    }
+
+    /// Reads one [`Riddle`] per line (s. [`Riddle::parse_line`]), e.g. from a file \
+    /// Empty lines are skipped
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// let riddles = Riddle::parse_many("2-7=3 1\n\n6+4=4 1\n".as_bytes()).unwrap();
+    ///
+    /// assert_eq!(2, riddles.len());
+    /// assert_eq!(
+    ///     &"6+4=4".parse::<Equation>().unwrap(),
+    ///     riddles[1].get_riddle_equation()
+    /// );
+    /// ```
+    pub fn parse_many(reader: impl BufRead) -> Result<Vec<Self>, ParseError> {
+        let mut riddles = Vec::new();
+        for line in reader.lines() {
+            let line = line.map_err(|error| ParseError::ReadFailed(error.kind()))?;
+            if !line.trim().is_empty() {
+                riddles.push(Self::parse_line(&line)?);
+            }
+        }
+
+        Ok(riddles)
+    }
+
+    /// Getter function for riddle [`Equation`] \
+    /// Together with [`Riddle::get_number_matchstick_movements`] this allows to render
+    /// or store the riddle of a given [`crate::puzzle::Puzzle`]
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// let puzzle = Puzzle::new_from_riddle(Riddle::new("7-3=4".parse().unwrap(), 1));
+    /// let riddle = puzzle.get_riddle();
+    ///
+    /// assert_eq!(&"7-3=4".parse::<Equation>().unwrap(), riddle.get_riddle_equation());
+    /// assert_eq!(&1, riddle.get_number_matchstick_movements());
+    /// ```
+    pub fn get_riddle_equation(&self) -> &Equation {
+        &self.riddle_equation
+    }
+
+    /// Getter function for number of matchstick movements
+    pub fn get_number_matchstick_movements(&self) -> &usize {
+        &self.number_matchstick_movements
+    }
+
+    /// Set which [`VariantPolicy`] is applied to the solution equations when solving
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::{Riddle, VariantPolicy};
+    /// # use matchstick::symbol::Symbol;
+    /// // Equation is "1 = +" and two matchsticks must be moved
+    /// let mut riddle = Riddle::new(
+    ///     Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::Plus]),
+    ///     2,
+    /// );
+    /// // "+" can become either variant of one
+    /// assert_eq!(2, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    ///
+    /// riddle.set_variant_policy(VariantPolicy::PreferVar1);
+    /// assert_eq!(1, Puzzle::new_from_riddle(riddle).search_and_set_solution());
+    /// ```
+    pub fn set_variant_policy(&mut self, variant_policy: VariantPolicy) {
+        self.variant_policy = variant_policy;
+    }
+
+    /// Getter function for [`VariantPolicy`]
+    pub fn get_variant_policy(&self) -> &VariantPolicy {
+        &self.variant_policy
+    }
+
+    /// Set which [`ValidationOptions`] a solution equation must fulfill
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::equation::validation::ValidationOptions;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::symbol::Symbol;
+    /// // Equation is "61 = 1" and one matchstick must be moved
+    /// let mut riddle = Riddle::new(
+    ///     Equation::new_from_symbols(vec![
+    ///         Symbol::Six,
+    ///         Symbol::OneVar1,
+    ///         Symbol::Equal,
+    ///         Symbol::OneVar1,
+    ///     ]),
+    ///     1,
+    /// );
+    /// // The only solution is "01 = 1"
+    /// assert_eq!(1, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    ///
+    /// riddle.set_validation_options(ValidationOptions {
+    ///     allow_leading_zeros: false,
+    ///     ..Default::default()
+    /// });
+    /// assert_eq!(0, Puzzle::new_from_riddle(riddle).search_and_set_solution());
+    /// ```
+    pub fn set_validation_options(&mut self, validation_options: ValidationOptions) {
+        self.validation_options = validation_options;
+    }
+
+    /// Getter function for [`ValidationOptions`]
+    pub fn get_validation_options(&self) -> &ValidationOptions {
+        &self.validation_options
+    }
+
+    /// Set whether solution equations are ranked by closeness to the riddle equation \
+    /// Ranked solution equations are sorted ascending by the number of segments changing state,
+    /// equally close solution equations are sorted by their [`crate::symbol::Symbol`]s \
+    /// Otherwise solution equations are kept in the order they are found
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// let mut riddle = Riddle::new("6+4=4".parse().unwrap(), 1);
+    /// riddle.set_rank_solutions(true);
+    /// let mut puzzle = Puzzle::new_from_riddle(riddle);
+    /// puzzle.search_and_set_solution();
+    ///
+    /// let SolutionWrapper::ProgrammaticallySet(solution) = puzzle.get_wrapped_solution() else {
+    ///     panic!("solution should be set");
+    /// };
+    /// let expected_equations: Vec<Equation> =
+    ///     vec!["8-4=4".parse().unwrap(), "0+4=4".parse().unwrap()];
+    /// assert_eq!(&expected_equations, solution.get_solution_equations());
+    /// ```
+    pub fn set_rank_solutions(&mut self, rank_solutions: bool) {
+        self.rank_solutions = rank_solutions;
+    }
+
+    /// Getter function for whether solution equations are ranked
+    pub fn get_rank_solutions(&self) -> &bool {
+        &self.rank_solutions
+    }
+
+    /// Set which [`PuzzleGoal`] a solution equation must achieve
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::{PuzzleGoal, Riddle};
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// // Form the expression with the largest value by moving one matchstick of "6 - 3"
+    /// let mut riddle = Riddle::new("6-3".parse().unwrap(), 1);
+    /// riddle.set_goal(PuzzleGoal::Maximize);
+    /// let mut puzzle = Puzzle::new_from_riddle(riddle);
+    /// puzzle.search_and_set_solution();
+    ///
+    /// let SolutionWrapper::ProgrammaticallySet(solution) = puzzle.get_wrapped_solution() else {
+    ///     panic!("solution should be set");
+    /// };
+    /// let expected_equations: Vec<Equation> = vec!["5+3".parse().unwrap()];
+    /// assert_eq!(&expected_equations, solution.get_solution_equations());
+    /// ```
+    pub fn set_goal(&mut self, goal: PuzzleGoal) {
+        self.goal = goal;
+    }
+
+    /// Getter function for [`PuzzleGoal`]
+    pub fn get_goal(&self) -> &PuzzleGoal {
+        &self.goal
+    }
+
+    /// Set whether the number of matchstick movements is an upper limit
+    /// instead of the exact number \
+    /// Solution equations formed with different numbers of movements are only listed once.
+    /// Zero movements are included, so a riddle equation that already achieves
+    /// the [`PuzzleGoal`] is a solution itself.
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "3 = 8" becomes "9 = 9" with one matchstick movement
+    /// let mut riddle = Riddle::new("3=8".parse().unwrap(), 2);
+    /// assert!(!riddle.accepts(&"9=9".parse().unwrap()));
+    ///
+    /// riddle.set_up_to_n_movements(true);
+    /// assert!(riddle.accepts(&"9=9".parse().unwrap()));
+    /// ```
+    pub fn set_up_to_n_movements(&mut self, up_to_n_movements: bool) {
+        self.up_to_n_movements = up_to_n_movements;
+    }
+
+    /// Getter function for whether the number of matchstick movements is an upper limit
+    pub fn get_up_to_n_movements(&self) -> &bool {
+        &self.up_to_n_movements
+    }
+
+    /// Set whether solution equations must consist of as many symbols as the riddle equation \
+    /// This is the default and keeps the classic puzzle layout.
+    /// Moving matchsticks within the existing symbols never changes the number of symbols,
+    /// so this only filters solutions once symbols can be added or removed
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// let mut riddle = Riddle::new("3=8".parse().unwrap(), 1);
+    /// assert!(*riddle.get_preserve_length());
+    ///
+    /// riddle.set_preserve_length(false);
+    /// assert!(!*riddle.get_preserve_length());
+    /// ```
+    pub fn set_preserve_length(&mut self, preserve_length: bool) {
+        self.preserve_length = preserve_length;
+    }
+
+    /// Getter function for whether solution equations keep the number of symbols
+    pub fn get_preserve_length(&self) -> &bool {
+        &self.preserve_length
+    }
+
+    /// Set positions of the riddle equation whose symbols must stay unchanged when solving,
+    /// e.g. the equal sign of a guided puzzle \
+    /// No matchstick is taken from or put onto a locked symbol. Positions outside of the
+    /// riddle equation are ignored
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "6 + 4 = 4" is solved by "8 - 4 = 4" and "0 + 4 = 4"
+    /// let mut riddle = Riddle::new("6+4=4".parse().unwrap(), 1);
+    /// assert_eq!(2, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    ///
+    /// // keeping the plus sign only allows "0 + 4 = 4"
+    /// riddle.set_locked_positions(vec![1]);
+    /// assert_eq!(1, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    /// assert!(!riddle.accepts(&"8-4=4".parse().unwrap()));
+    /// ```
+    pub fn set_locked_positions(&mut self, locked_positions: Vec<usize>) {
+        self.locked_positions = locked_positions;
+    }
+
+    /// Getter function for positions whose symbols stay unchanged
+    pub fn get_locked_positions(&self) -> &Vec<usize> {
+        &self.locked_positions
+    }
+
+    /// Set whether solution equations only differing in the order of summed terms
+    /// are listed once, e.g. "2+3=5" and "3+2=5" \
+    /// Each expression is read as a sum of signed terms, so "5-3=2" and "-3+5=2" are
+    /// collapsed as well, while the sides of the equal sign keep their order.
+    /// The first found solution equation is kept
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "3 + 3 = 5" is solved by "2 + 3 = 5" and "3 + 2 = 5"
+    /// let mut riddle = Riddle::new("3+3=5".parse().unwrap(), 1);
+    /// assert_eq!(2, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    ///
+    /// riddle.set_collapse_commutative(true);
+    /// assert_eq!(1, Puzzle::new_from_riddle(riddle).search_and_set_solution());
+    /// ```
+    pub fn set_collapse_commutative(&mut self, collapse_commutative: bool) {
+        self.collapse_commutative = collapse_commutative;
+    }
+
+    /// Getter function for whether solution equations differing in the order of terms are collapsed
+    pub fn get_collapse_commutative(&self) -> &bool {
+        &self.collapse_commutative
+    }
+
+    /// Set whether the equal signs of solution equations must stay at the positions
+    /// of the equal signs in the riddle equation \
+    /// Moving matchsticks may turn an equal sign into a minus and another symbol into
+    /// an equal sign, which is ruled out by this
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "5 - 7 = 2" becomes "5 = 7 - 2" by moving one matchstick from the equal sign
+    /// // onto the minus
+    /// let mut riddle = Riddle::new("5-7=2".parse().unwrap(), 1);
+    /// assert!(riddle.accepts(&"5=7-2".parse().unwrap()));
+    ///
+    /// riddle.set_fix_equals_position(true);
+    /// assert!(!riddle.accepts(&"5=7-2".parse().unwrap()));
+    /// ```
+    pub fn set_fix_equals_position(&mut self, fix_equals_position: bool) {
+        self.fix_equals_position = fix_equals_position;
+    }
+
+    /// Getter function for whether equal signs stay at their positions
+    pub fn get_fix_equals_position(&self) -> &bool {
+        &self.fix_equals_position
+    }
+
+    /// Set the [`EquationPattern`] solution equations must fulfill, e.g. "N+N=N"
+    /// for a beginner mode \
+    /// Formed equations not fulfilling it are no solutions, unlike with
+    /// [`crate::puzzle::puzzle_generator::PuzzleGenerator::set_solution_equation_pattern`]
+    /// which skips the whole riddle
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "6 + 4 = 4" is solved by "8 - 4 = 4" and "0 + 4 = 4"
+    /// let mut riddle = Riddle::new("6+4=4".parse().unwrap(), 1);
+    /// riddle.set_solution_equation_pattern("N+N=N".parse().unwrap());
+    ///
+    /// assert_eq!(1, Puzzle::new_from_riddle(riddle.clone()).search_and_set_solution());
+    /// assert!(!riddle.accepts(&"8-4=4".parse().unwrap()));
+    /// ```
+    pub fn set_solution_equation_pattern(&mut self, solution_equation_pattern: EquationPattern) {
+        self.solution_equation_pattern = Some(solution_equation_pattern);
+    }
+
+    /// Getter function for the [`EquationPattern`] of solution equations
+    pub fn get_solution_equation_pattern(&self) -> &Option<EquationPattern> {
+        &self.solution_equation_pattern
+    }
+
+    /// Checks whether the candidate [`Equation`] solves the [`Riddle`] \
+    /// It must achieve the [`PuzzleGoal`], e.g. be mathematically valid,
+    /// and be formed from the riddle equation by moving exactly the required number of matchsticks
+    /// (or at most, s. [`Riddle::set_up_to_n_movements`])
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // Equation is "7 - 3 = 4" and one matchstick must be moved
+    /// let riddle = Riddle::new("7-3=4".parse().unwrap(), 1);
+    ///
+    /// assert!(riddle.accepts(&"1+3=4".parse().unwrap()));
+    /// // mathematically valid, but no matchstick was moved
+    /// assert!(!riddle.accepts(&"7-3=4".parse().unwrap()));
+    /// // one matchstick was moved, but mathematically invalid
+    /// assert!(!riddle.accepts(&"7+3=4".parse().unwrap()));
+    /// ```
+    pub fn accepts(&self, candidate: &Equation) -> bool {
+        if self.filter_for_goal(vec![candidate.clone()]).is_empty() {
+            return false;
+        }
+        if self.goal == PuzzleGoal::Maximize {
+            // whether the candidate has the largest value can only be known by solving
+            if let SolutionWrapper::ProgrammaticallySet(solution) = self.solve() {
+                return solution.get_solution_equations().contains(candidate);
+            }
+        }
+
+        if !self.fulfills_solution_restrictions(candidate) {
+            return false;
+        }
+        let Some(transition_sequence) = self.riddle_equation.transition_to(candidate) else {
+            return false;
+        };
+        if !transition_sequence.leaves_unchanged(&self.locked_positions) {
+            return false;
+        }
+        // moved matchsticks are removed at one place and added at another
+        let number_moved = transition_sequence.count_removed();
+        if number_moved != transition_sequence.count_added() {
+            return false;
+        }
+
+        match self.up_to_n_movements {
+            true => number_moved <= self.number_matchstick_movements,
+            false => number_moved == self.number_matchstick_movements,
         }
     }
 
     // Programmatically search for a solution
     pub(super) fn solve(&self) -> SolutionWrapper {
-        let transformed_equations = self
-            .riddle_equation
-            .move_n_matchsticks(self.number_matchstick_movements);
-        let solution_equations = transformed_equations
+        self.solve_with_cache(&mut TransitionSequenceCache::default())
+    }
+
+    // Like [`Riddle::solve`] but the transition sequences are taken from the cache
+    pub(super) fn solve_with_cache(
+        &self,
+        transition_sequence_cache: &mut TransitionSequenceCache,
+    ) -> SolutionWrapper {
+        let solution_equations = self.find_solution_equations(transition_sequence_cache);
+        let solution_equations = self
+            .variant_policy
+            .select(&self.riddle_equation, solution_equations);
+
+        SolutionWrapper::new_programmatically_set_solution(
+            self.collapse_and_rank(solution_equations),
+        )
+    }
+
+    /// Programmatically search for a solution and describe how each distinct solution is reached \
+    /// There is one [`DetailedSolution`] per solution value, represented by the equation
+    /// the [`VariantPolicy`] selects (the first found one for [`VariantPolicy::All`]).
+    /// Its moves hold the [`TransitionSequence`]s to every variant of that value
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "1 = 1" is found twice using both variants of the second one
+    /// let riddle = Riddle::new("1=+".parse().unwrap(), 2);
+    /// let detailed_solutions = riddle.solve_detailed();
+    ///
+    /// assert_eq!(1, detailed_solutions.len());
+    /// assert_eq!(
+    ///     &"1=1".parse::<Equation>().unwrap(),
+    ///     detailed_solutions[0].get_equation()
+    /// );
+    /// assert_eq!(2, detailed_solutions[0].get_moves().len());
+    /// ```
+    pub fn solve_detailed(&self) -> Vec<DetailedSolution> {
+        let solution_equations =
+            self.find_solution_equations(&mut TransitionSequenceCache::default());
+        let representatives = self.collapse_and_rank(
+            self.variant_policy
+                .select(&self.riddle_equation, solution_equations.clone()),
+        );
+
+        // without a variant policy several representatives can share a value
+        let mut plain_texts = HashSet::new();
+        representatives
             .into_iter()
-            .filter_map(|equation| match equation.mathematically_validate() {
-                Ok(()) => Some(equation),
-                Err(()) => None,
+            .filter(|representative| plain_texts.insert(representative.to_plain_text()))
+            .map(|representative| {
+                let moves = solution_equations
+                    .iter()
+                    .filter(|equation| equation.to_plain_text() == representative.to_plain_text())
+                    .filter_map(|equation| self.riddle_equation.transition_to(equation))
+                    .collect();
+                DetailedSolution::new(representative, moves)
+            })
+            .collect()
+    }
+
+    // Equations formed by moving matchsticks that keep the layout and achieve the goal,
+    // including all variants
+    fn find_solution_equations(
+        &self,
+        transition_sequence_cache: &mut TransitionSequenceCache,
+    ) -> Vec<Equation> {
+        let mut transformed_equations = match self.up_to_n_movements {
+            true => self.riddle_equation.move_up_to_n_matchsticks_with_cache(
+                self.number_matchstick_movements,
+                &self.locked_positions,
+                transition_sequence_cache,
+            ),
+            false => self.riddle_equation.move_n_matchsticks_with_cache(
+                self.number_matchstick_movements,
+                &self.locked_positions,
+                transition_sequence_cache,
+            ),
+        };
+        // solution equations keep the layout of the riddle equation and match the solution pattern
+        transformed_equations.retain(|equation| self.fulfills_solution_restrictions(equation));
+        self.filter_for_goal(transformed_equations)
+    }
+
+    // Drops commutative duplicates and sorts by closeness to the riddle equation, if enabled
+    fn collapse_and_rank(&self, mut solution_equations: Vec<Equation>) -> Vec<Equation> {
+        if self.collapse_commutative {
+            let mut commutative_keys = HashSet::new();
+            solution_equations
+                .retain(|equation| commutative_keys.insert(equation.commutative_key()));
+        }
+        if self.rank_solutions {
+            solution_equations.sort_by(|equation, other_equation| {
+                self.riddle_equation
+                    .segment_delta_to(equation)
+                    .cmp(&self.riddle_equation.segment_delta_to(other_equation))
+                    .then_with(|| equation.cmp(other_equation))
+            });
+        }
+        solution_equations
+    }
+
+    // Keeps the equations that achieve the goal of the riddle
+    fn filter_for_goal(&self, equations: Vec<Equation>) -> Vec<Equation> {
+        match self.goal {
+            PuzzleGoal::TrueEquation => {
+                // the same equation can be formed by several matchstick movements
+                let mut validation_cache = ValidationCache::new(self.validation_options);
+                equations
+                    .into_iter()
+                    .filter(|equation| validation_cache.validate(equation).is_ok())
+                    .collect()
+            }
+            PuzzleGoal::Maximize => {
+                let evaluated_equations = equations
+                    .into_iter()
+                    .filter_map(|equation| {
+                        let value = equation.evaluate_single()?;
+                        Some((equation, value))
+                    })
+                    .collect::<Vec<_>>();
+                let max_value = evaluated_equations.iter().map(|(_, value)| *value).max();
+
+                evaluated_equations
+                    .into_iter()
+                    .filter(|(_, value)| Some(*value) == max_value)
+                    .map(|(equation, _)| equation)
+                    .collect()
+            }
+            PuzzleGoal::ReachTarget(target) => equations
+                .into_iter()
+                .filter(|equation| equation.evaluates_to(target))
+                .collect(),
+        }
+    }
+
+    /// Checks whether the [`Riddle`] has at least one solution \
+    /// Unlike solving, this stops at the first equation achieving the [`PuzzleGoal`],
+    /// so riddles with many solutions are checked quickly
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "7 - 3 = 4" becomes "1 + 3 = 4" by moving one matchstick
+    /// assert!(Riddle::new("7-3=4".parse().unwrap(), 1).is_solvable());
+    /// // one matchstick can't be moved within a single "1"
+    /// assert!(!Riddle::new("1".parse().unwrap(), 1).is_solvable());
+    /// ```
+    pub fn is_solvable(&self) -> bool {
+        let mut validation_cache = ValidationCache::new(self.validation_options);
+
+        self.iter_transformed_equations()
+            .any(|equation| match self.goal {
+                PuzzleGoal::TrueEquation => validation_cache.validate(&equation).is_ok(),
+                // every evaluable expression is a candidate for the largest value
+                PuzzleGoal::Maximize => equation.evaluate_single().is_some(),
+                PuzzleGoal::ReachTarget(target) => equation.evaluates_to(target),
+            })
+    }
+
+    /// The formed equation whose sides are closest to being equal, together with
+    /// the absolute difference of their values, e.g. to give feedback on unsolvable riddles \
+    /// With several equal signs, the largest difference to the first side counts.
+    /// A difference of 0 means the riddle is solvable, although [`ValidationOptions`] are
+    /// not checked. Returns `None` if no formed equation can be evaluated
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "1 + 1 = 7" can't be solved by moving one matchstick, but "7 - 1 = 7" is off by one
+    /// let riddle = Riddle::new("1+1=7".parse().unwrap(), 1);
+    /// assert!(!riddle.is_solvable());
+    ///
+    /// let (equation, difference) = riddle.closest_near_miss().unwrap();
+    /// assert_eq!("7-1=7".parse::<Equation>().unwrap(), equation);
+    /// assert_eq!(1, difference);
+    /// ```
+    pub fn closest_near_miss(&self) -> Option<(Equation, i64)> {
+        self.iter_transformed_equations()
+            .filter_map(|equation| {
+                let difference = equation.side_difference()?;
+                Some((equation, difference))
+            })
+            .min_by_key(|(_, difference)| *difference)
+    }
+
+    // Lazily forms the equations that are checked against the goal when solving,
+    // e.g. to stop at the first solution
+    fn iter_transformed_equations(&self) -> impl Iterator<Item = Equation> {
+        let numbers_movements = match self.up_to_n_movements {
+            true => 0..=self.number_matchstick_movements,
+            false => self.number_matchstick_movements..=self.number_matchstick_movements,
+        };
+
+        numbers_movements
+            .flat_map(|number_movements| {
+                self.riddle_equation
+                    .iter_move_n_matchsticks(number_movements, &self.locked_positions)
             })
-            .collect();
+            .filter(|equation| self.fulfills_solution_restrictions(equation))
+    }
+
+    // Whether the equation may be a solution regardless of the goal,
+    // i.e. it keeps the layout and matches the solution pattern if given
+    fn fulfills_solution_restrictions(&self, equation: &Equation) -> bool {
+        self.keeps_layout(equation) && self.matches_solution_pattern(equation)
+    }
+
+    // Whether the equation keeps the layout of the riddle equation as far as required,
+    // i.e. its number of symbols and the positions of its equal signs
+    fn keeps_layout(&self, equation: &Equation) -> bool {
+        let equals_positions = |equation: &Equation| {
+            equation
+                .get_symbols()
+                .iter()
+                .positions(|symbol| *symbol == Symbol::Equal)
+                .collect::<Vec<_>>()
+        };
+
+        (!self.preserve_length
+            || equation.get_number_of_symbols() == self.riddle_equation.get_number_of_symbols())
+            && (!self.fix_equals_position
+                || equals_positions(equation) == equals_positions(&self.riddle_equation))
+    }
+
+    // Whether the equation fulfills the solution pattern, if one is given
+    fn matches_solution_pattern(&self, equation: &Equation) -> bool {
+        self.solution_equation_pattern
+            .as_ref()
+            .is_none_or(|pattern| equation.matches_pattern(pattern))
+    }
+
+    /// How many equations are formed by moving matchsticks before they are checked
+    /// against the [`PuzzleGoal`] \
+    /// This allows to estimate the cost of solving. If the number of matchstick movements is
+    /// an upper limit, equations formed with different numbers of movements are all counted
+    /// ```
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// // "3" can become "2" or "5" with one matchstick movement
+    /// let riddle = Riddle::new("3".parse().unwrap(), 1);
+    /// assert_eq!(2, riddle.candidate_count());
+    /// ```
+    pub fn candidate_count(&self) -> usize {
+        match self.up_to_n_movements {
+            true => (0..=self.number_matchstick_movements)
+                .map(|number_movements| {
+                    self.riddle_equation
+                        .count_move_n_matchsticks(number_movements, &self.locked_positions)
+                })
+                .sum(),
+            false => self
+                .riddle_equation
+                .count_move_n_matchsticks(self.number_matchstick_movements, &self.locked_positions),
+        }
+    }
+
+    /// Programmatically search for a solution unless more than `max_candidates`
+    /// matchstick movement patterns would need to be examined \
+    /// This bounds the runtime for long equations and many matchstick movements
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::riddle::{Riddle, SolveError};
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// # use matchstick::symbol::Symbol;
+    /// let equation = Equation::new_from_symbols(vec![
+    ///     Symbol::Seven,
+    ///     Symbol::Minus,
+    ///     Symbol::Three,
+    ///     Symbol::Equal,
+    ///     Symbol::FourVar1,
+    /// ]);
+    ///
+    /// // one movement of five symbols results in 25 movement patterns
+    /// let riddle = Riddle::new(equation.clone(), 1);
+    /// assert!(matches!(
+    ///     riddle.solve_with_limit(100),
+    ///     Ok(SolutionWrapper::ProgrammaticallySet(_))
+    /// ));
+    ///
+    /// // three movements of five symbols result in 15625 movement patterns
+    /// let riddle = Riddle::new(equation, 3);
+    /// assert_eq!(Err(SolveError::TooManyCandidates), riddle.solve_with_limit(100));
+    /// ```
+    pub fn solve_with_limit(&self, max_candidates: usize) -> Result<SolutionWrapper, SolveError> {
+        let number_symbols = self.riddle_equation.get_number_of_symbols();
+        let number_candidates = match self.up_to_n_movements {
+            true => (0..=self.number_matchstick_movements).try_fold(0, |sum, number_movements| {
+                TransitionSequence::count_move_n(number_movements, number_symbols)?.checked_add(sum)
+            }),
+            false => {
+                TransitionSequence::count_move_n(self.number_matchstick_movements, number_symbols)
+            }
+        };
+
+        match number_candidates {
+            Some(number_candidates) if number_candidates <= max_candidates => Ok(self.solve()),
+            _ => Err(SolveError::TooManyCandidates),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn count_candidates_like_formed_equations() {
+        for (riddle_equation, number_matchstick_movements) in [("6+4=4", 1), ("9-5=4", 2)] {
+            let riddle_equation: Equation = riddle_equation.parse().unwrap();
+            let mut riddle = Riddle::new(riddle_equation.clone(), number_matchstick_movements);
+
+            assert_eq!(
+                riddle_equation
+                    .move_n_matchsticks(number_matchstick_movements)
+                    .len(),
+                riddle.candidate_count()
+            );
+
+            riddle.set_up_to_n_movements(true);
+            let number_formed_equations = (0..=number_matchstick_movements)
+                .map(|number_movements| riddle_equation.move_n_matchsticks(number_movements).len())
+                .sum::<usize>();
+            assert_eq!(number_formed_equations, riddle.candidate_count());
+        }
+    }
+
+    #[test]
+    fn parse_riddle_lines() {
+        assert_eq!(
+            Ok(Riddle::new("2-7=3".parse().unwrap(), 2)),
+            Riddle::parse_line(" 2-7=3  2 ")
+        );
+        assert_eq!(
+            Err(ParseError::MissingMoveCount),
+            Riddle::parse_line(" 2-7=3 ")
+        );
+        for line in ["2 - 7 = 3", "2 - 7 = 3 1"] {
+            assert_eq!(
+                Err(ParseError::InvalidRiddleLine(line.to_string())),
+                Riddle::parse_line(line)
+            );
+        }
+        assert_eq!(
+            Err(ParseError::InvalidMoveCount("x".to_string())),
+            Riddle::parse_line("2-7=3 x")
+        );
+        assert_eq!(
+            Err(ParseError::UnknownCharacter('a')),
+            Riddle::parse_many("2-7=3 1\na=3 1".as_bytes())
+        );
+    }
+
+    #[test]
+    fn no_difference_for_solvable_riddle() {
+        let riddle = Riddle::new("6+4=4".parse().unwrap(), 1);
+        let (equation, difference) = riddle.closest_near_miss().unwrap();
+
+        assert_eq!(0, difference);
+        assert!(riddle.accepts(&equation));
+        // no equal sign can be formed, so there are no sides to compare
+        assert_eq!(
+            None,
+            Riddle::new("64".parse().unwrap(), 1).closest_near_miss()
+        );
+    }
+
+    #[test]
+    fn reach_target_value() {
+        // "6 - 3" becomes "5 + 3" by moving one matchstick of the six onto the minus
+        let mut riddle = Riddle::new("6-3".parse().unwrap(), 1);
+        riddle.set_goal(PuzzleGoal::ReachTarget(8));
+
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        let expected_equations: Vec<Equation> = vec!["5+3".parse().unwrap()];
+        assert_eq!(&expected_equations, solution.get_solution_equations());
+        assert!(riddle.is_solvable());
+        assert!(riddle.accepts(&"5+3".parse().unwrap()));
+
+        riddle.set_goal(PuzzleGoal::ReachTarget(1000));
+        assert!(!riddle.is_solvable());
+    }
+
+    #[test]
+    fn solvable_like_solution_found() {
+        for (riddle_equation, number_matchstick_movements) in
+            [("6+4=4", 1), ("9-5=4", 2), ("1+1=1", 1), ("8=8", 1)]
+        {
+            let riddle = Riddle::new(
+                riddle_equation.parse().unwrap(),
+                number_matchstick_movements,
+            );
+            let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+                panic!("solution should be set");
+            };
+
+            assert_eq!(
+                !solution.get_solution_equations().is_empty(),
+                riddle.is_solvable(),
+                "{riddle_equation}"
+            );
+        }
+    }
+
+    #[test]
+    fn keep_equal_signs_in_place() {
+        // "1 - 7 = 6" is also solved by "1 = 7 - 6"
+        let mut riddle = Riddle::new("1-7=6".parse().unwrap(), 1);
+        assert!(riddle.accepts(&"1=7-6".parse().unwrap()));
+        riddle.set_fix_equals_position(true);
+
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        assert_eq!(1, solution.get_solution_equations().len());
+        assert!(
+            solution
+                .get_solution_equations()
+                .iter()
+                .all(|equation| equation.get_symbols()[3] == Symbol::Equal
+                    && !equation.get_symbols()[..3].contains(&Symbol::Equal))
+        );
+        assert!(riddle.is_solvable());
+    }
+
+    #[test]
+    fn keep_solutions_fulfilling_pattern() {
+        let mut riddle = Riddle::new("9-5=4".parse().unwrap(), 2);
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        let solution_equation_pattern: EquationPattern = "N-N=N".parse().unwrap();
+        let expected_equations = solution
+            .get_solution_equations()
+            .iter()
+            .filter(|equation| equation.matches_pattern(&solution_equation_pattern))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        riddle.set_solution_equation_pattern(solution_equation_pattern);
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        // "5 = 9 - 4" has its equal sign elsewhere
+        assert!(!expected_equations.is_empty());
+        assert!(!expected_equations.contains(&"5=9-4".parse().unwrap()));
+        assert_eq!(&expected_equations, solution.get_solution_equations());
+        assert!(riddle.is_solvable());
+    }
+
+    #[test]
+    fn leave_locked_symbol_unchanged() {
+        // "3 = 8" becomes "9 = 9" when the three isn't locked
+        let mut riddle = Riddle::new("3=8".parse().unwrap(), 1);
+        assert!(riddle.accepts(&"9=9".parse().unwrap()));
+
+        riddle.set_locked_positions(vec![0, 7]);
+        assert!(!riddle.accepts(&"9=9".parse().unwrap()));
+        let formed_equations = riddle.riddle_equation.move_n_matchsticks(1);
+        assert_eq!(
+            formed_equations
+                .iter()
+                .filter(|equation| equation.get_symbols()[0] == Symbol::Three)
+                .count(),
+            riddle.candidate_count()
+        );
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        assert!(
+            solution
+                .get_solution_equations()
+                .iter()
+                .all(|equation| equation.get_symbols()[0] == Symbol::Three)
+        );
+    }
+
+    #[test]
+    fn accept_only_exact_number_of_movements() {
+        // "6 + 4 = 4" can be solved with one matchstick movement
+        let riddle_equation = Equation::new_from_symbols(vec![
+            Symbol::Six,
+            Symbol::Plus,
+            Symbol::FourVar1,
+            Symbol::Equal,
+            Symbol::FourVar1,
+        ]);
+        // "8 - 4 = 4"
+        let candidate = Equation::new_from_symbols(vec![
+            Symbol::EightVar1,
+            Symbol::Minus,
+            Symbol::FourVar1,
+            Symbol::Equal,
+            Symbol::FourVar1,
+        ]);
+
+        assert!(Riddle::new(riddle_equation.clone(), 1).accepts(&candidate));
+        assert!(!Riddle::new(riddle_equation.clone(), 2).accepts(&candidate));
+        assert!(!Riddle::new(riddle_equation, 0).accepts(&candidate));
+    }
+
+    #[test]
+    fn maximize_expression() {
+        let mut riddle = Riddle::new(
+            Equation::new_from_symbols(vec![Symbol::Nine, Symbol::Minus, Symbol::OneVar1]),
+            1,
+        );
+        riddle.set_goal(PuzzleGoal::Maximize);
+
+        // "5 + 1" is the largest, "9 + 1" isn't formed by a movement
+        let expected_solution =
+            SolutionWrapper::new_programmatically_set_solution(vec![Equation::new_from_symbols(
+                vec![Symbol::Five, Symbol::Plus, Symbol::OneVar1],
+            )]);
+        assert_eq!(expected_solution, riddle.solve());
+
+        assert!(riddle.accepts(&Equation::new_from_symbols(vec![
+            Symbol::Five,
+            Symbol::Plus,
+            Symbol::OneVar1,
+        ])));
+        // "3 + 1" is formed by one movement but doesn't have the largest value
+        assert!(!riddle.accepts(&Equation::new_from_symbols(vec![
+            Symbol::Three,
+            Symbol::Plus,
+            Symbol::OneVar1,
+        ])));
+    }
+
+    #[test]
+    fn solve_with_up_to_n_movements() {
+        // "3 + 3 = 5" needs one matchstick movement
+        let riddle_equation = Equation::new_from_symbols(vec![
+            Symbol::Three,
+            Symbol::Plus,
+            Symbol::Three,
+            Symbol::Equal,
+            Symbol::Five,
+        ]);
+        let mut riddle = Riddle::new(riddle_equation, 2);
+        let SolutionWrapper::ProgrammaticallySet(exact_solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        riddle.set_up_to_n_movements(true);
+        let SolutionWrapper::ProgrammaticallySet(up_to_solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+
+        // "2 + 3 = 5" is formed with one movement
+        let solution_with_one_movement = Equation::new_from_symbols(vec![
+            Symbol::Two,
+            Symbol::Plus,
+            Symbol::Three,
+            Symbol::Equal,
+            Symbol::Five,
+        ]);
+        assert!(
+            !exact_solution
+                .get_solution_equations()
+                .contains(&solution_with_one_movement)
+        );
+        assert!(
+            up_to_solution
+                .get_solution_equations()
+                .contains(&solution_with_one_movement)
+        );
+
+        // all solution equations with two movements are still found, but only once
+        for equation in exact_solution.get_solution_equations() {
+            assert!(up_to_solution.get_solution_equations().contains(equation));
+        }
+        let number_unique_equations = up_to_solution
+            .get_solution_equations()
+            .iter()
+            .collect::<std::collections::HashSet<_>>()
+            .len();
+        assert_eq!(
+            number_unique_equations,
+            up_to_solution.get_solution_equations().len()
+        );
+    }
+
+    #[test]
+    fn solve_valid_riddle_with_up_to_n_movements() {
+        let riddle_equation =
+            Equation::new_from_symbols(vec![Symbol::Two, Symbol::Equal, Symbol::Two]);
+        let mut riddle = Riddle::new(riddle_equation.clone(), 1);
+        riddle.set_up_to_n_movements(true);
+
+        // the riddle equation is formed with zero movements
+        assert!(riddle.accepts(&riddle_equation));
+        let SolutionWrapper::ProgrammaticallySet(solution) = riddle.solve() else {
+            panic!("solution should be set");
+        };
+        assert_eq!(
+            Some(&riddle_equation),
+            solution.get_solution_equations().first()
+        );
+    }
+
+    #[test]
+    fn form_parentheses_only_if_riddle_contains_some() {
+        // digits could turn into "(4)=4" but the riddles contain no parentheses
+        for plain_text in ["0+7=4", "6+7=4"] {
+            let riddle = Riddle::new(plain_text.parse().unwrap(), 2);
+            assert_eq!(
+                SolutionWrapper::new_programmatically_set_solution(vec![]),
+                riddle.solve(),
+                "{plain_text}"
+            );
+        }
+
+        // the zero and the seven turn into parentheses, the plus into a four
+        let riddle = Riddle::new("(0+7)=4".parse().unwrap(), 2);
+        let wrapped_solution = riddle.solve();
+        let solution = wrapped_solution.get_inner_reference().unwrap();
+        assert_eq!(vec!["((4))=4"], solution.signature());
+    }
+
+    #[test]
+    fn form_degree_signs_only_if_riddle_contains_some() {
+        // the second variant of eight could turn into a degree sign forming "7°=7"
+        let mut symbols = vec![
+            Symbol::OneVar1,
+            Symbol::EightVar2,
+            Symbol::Equal,
+            Symbol::Seven,
+        ];
+        let riddle = Riddle::new(Equation::new_from_symbols(symbols.clone()), 1);
+        assert_eq!(
+            SolutionWrapper::new_programmatically_set_solution(vec![]),
+            riddle.solve()
+        );
+
+        symbols.push(Symbol::Degree);
+        let riddle = Riddle::new(Equation::new_from_symbols(symbols), 1);
+        let wrapped_solution = riddle.solve();
+        let solution = wrapped_solution.get_inner_reference().unwrap();
+        assert_eq!(vec!["18=18", "7°=7°"], solution.signature());
+    }
+
+    #[test]
+    fn prefer_first_variant() {
+        let mut riddle = Riddle::new(
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::Plus]),
+            2,
+        );
+        riddle.set_variant_policy(VariantPolicy::PreferVar1);
+
+        let expected_solution =
+            SolutionWrapper::new_programmatically_set_solution(vec![Equation::new_from_symbols(
+                vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1],
+            )]);
+
+        assert_eq!(expected_solution, riddle.solve());
+    }
+
+    #[test]
+    fn keep_all_variants() {
+        let riddle = Riddle::new(
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::Plus]),
+            2,
+        );
+
+        let expected_solution = SolutionWrapper::new_programmatically_set_solution(vec![
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1]),
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar2]),
+        ]);
+
+        assert_eq!(expected_solution, riddle.solve());
+    }
+
+    #[test]
+    fn collect_moves_of_all_variants() {
+        let mut riddle = Riddle::new(
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::Plus]),
+            2,
+        );
+        riddle.set_variant_policy(VariantPolicy::PreferVar1);
+
+        let detailed_solutions = riddle.solve_detailed();
+        assert_eq!(1, detailed_solutions.len());
+        assert_eq!(
+            &Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1]),
+            detailed_solutions[0].get_equation()
+        );
+        // both variants of one are formed from plus by moving two matchsticks within it
+        let moves = detailed_solutions[0].get_moves();
+        assert_eq!(2, moves.len());
+        for transition_sequence in moves {
+            assert_eq!(2, transition_sequence.count_removed());
+            assert_eq!(2, transition_sequence.count_added());
+        }
+    }
+
+    #[test]
+    fn prefer_closest_variant() {
+        let mut riddle = Riddle::new(
+            Equation::new_from_symbols(vec![Symbol::OneVar1, Symbol::Equal, Symbol::Plus]),
+            2,
+        );
+        riddle.set_variant_policy(VariantPolicy::PreferClosest);
+
+        // both variants require the same segment changes, so the first found is kept
+        let expected_solution =
+            SolutionWrapper::new_programmatically_set_solution(vec![Equation::new_from_symbols(
+                vec![Symbol::OneVar1, Symbol::Equal, Symbol::OneVar1],
+            )]);
 
-        SolutionWrapper::new_programmatically_set_solution(solution_equations)
+        assert_eq!(expected_solution, riddle.solve());
     }
 }
@@ -1,4 +1,7 @@
+use itertools::Itertools;
+
 use crate::equation::Equation;
+use crate::transition::TransitionSequence;
 
 /// Holds information to describe the solution of a matchstick riddle
 #[derive(Debug, PartialEq)]
@@ -7,10 +10,67 @@ pub struct Solution {
 }
 
 impl Solution {
-    /// Getter function for riddle [`EquationPattern`]
-    pub(super) fn get_solution_equations(&self) -> &Vec<Equation> {
+    /// Getter function for solution [`Equation`]s
+    pub fn get_solution_equations(&self) -> &Vec<Equation> {
         &self.solution_equations
     }
+
+    /// Sorted, distinct texts of the solution [`Equation`]s (s. [`Equation::canonical_string`]) \
+    /// Solutions with the same signature hold the same equations regardless of their order
+    /// and symbol variants, e.g. to detect changed solutions in regression tests
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::puzzle::solution::SolutionWrapper;
+    /// # use matchstick::symbol::Symbol;
+    /// let equations = vec!["3=1".parse().unwrap(), "1=1".parse().unwrap()];
+    /// let wrapped_solution = SolutionWrapper::new_manually_set_solution(equations);
+    /// let SolutionWrapper::ManuallySet(solution) = &wrapped_solution else {
+    ///     panic!("solution should be set");
+    /// };
+    /// assert_eq!(vec!["1=1", "3=1"], solution.signature());
+    ///
+    /// let variant_equation =
+    ///     Equation::new_from_symbols(vec![Symbol::OneVar2, Symbol::Equal, Symbol::OneVar1]);
+    /// let equations = vec!["1=1".parse().unwrap(), "3=1".parse().unwrap(), variant_equation];
+    /// let other_wrapped_solution = SolutionWrapper::new_manually_set_solution(equations);
+    /// let SolutionWrapper::ManuallySet(other_solution) = &other_wrapped_solution else {
+    ///     panic!("solution should be set");
+    /// };
+    /// assert_eq!(solution.signature(), other_solution.signature());
+    /// ```
+    pub fn signature(&self) -> Vec<String> {
+        self.solution_equations
+            .iter()
+            .map(Equation::canonical_string)
+            .sorted()
+            .dedup()
+            .collect()
+    }
+}
+
+/// One solution value of a [`super::Riddle`] together with every way to reach it \
+/// Solution equations only differing in symbol variants share one [`DetailedSolution`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetailedSolution {
+    equation: Equation,
+    moves: Vec<TransitionSequence>,
+}
+
+impl DetailedSolution {
+    pub(super) fn new(equation: Equation, moves: Vec<TransitionSequence>) -> Self {
+        DetailedSolution { equation, moves }
+    }
+
+    /// Getter function for the representative solution [`Equation`]
+    pub fn get_equation(&self) -> &Equation {
+        &self.equation
+    }
+
+    /// Getter function for the [`TransitionSequence`]s turning the riddle equation
+    /// into any variant of the solution equation
+    pub fn get_moves(&self) -> &Vec<TransitionSequence> {
+        &self.moves
+    }
 }
 
 /// Wraps [`Solution`]s of a [`super::Riddle`]
@@ -0,0 +1,153 @@
+use super::Puzzle;
+use super::riddle::Riddle;
+use super::solution::SolutionWrapper;
+use crate::equation::Equation;
+use crate::equation::parse::ParseError;
+
+const RIDDLE_PREFIX: &str = "riddle: ";
+const MOVES_PREFIX: &str = "moves: ";
+const SOLUTIONS_HEADER: &str = "solutions:";
+
+/// Reasons why a text block can't be turned back into a [`Puzzle`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum TextBlockError {
+    /// The first line doesn't hold the riddle equation
+    MissingRiddle,
+    /// The second line doesn't hold the number of matchstick movements
+    MissingMoves,
+    /// The number of matchstick movements is not a number
+    InvalidMoves(String),
+    /// A line after the moves is neither the solutions header nor a solution equation
+    UnexpectedLine(String),
+    /// An equation can't be read
+    InvalidEquation(ParseError),
+}
+
+impl From<ParseError> for TextBlockError {
+    fn from(parse_error: ParseError) -> Self {
+        TextBlockError::InvalidEquation(parse_error)
+    }
+}
+
+impl Puzzle {
+    /// Readable text of the [`Puzzle`] to share it, e.g. in emails \
+    /// The first line holds the riddle equation and the second one the number of matchstick
+    /// movements. If the solution is set, the solution equations follow the line `solutions:`,
+    /// one per line. Digit variants are written like all other digits
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::riddle::Riddle;
+    /// let puzzle = Puzzle::solved_from_riddle(Riddle::new("7-3=4".parse().unwrap(), 1));
+    ///
+    /// assert_eq!("riddle: 7-3=4\nmoves: 1\nsolutions:\n1+3=4", puzzle.to_text_block());
+    /// ```
+    pub fn to_text_block(&self) -> String {
+        let mut text_block = format!(
+            "{RIDDLE_PREFIX}{}\n{MOVES_PREFIX}{}",
+            self.riddle.get_riddle_equation().to_plain_text(),
+            self.riddle.get_number_matchstick_movements()
+        );
+
+        if let Ok(solution) = self.wrapped_solution.get_inner_reference() {
+            text_block.push('\n');
+            text_block.push_str(SOLUTIONS_HEADER);
+            for solution_equation in solution.get_solution_equations() {
+                text_block.push('\n');
+                text_block.push_str(&solution_equation.to_plain_text());
+            }
+        }
+
+        text_block
+    }
+
+    /// Reads a [`Puzzle`] from its text block (s. [`Puzzle::to_text_block`]) \
+    /// Listed solutions are read as [`SolutionWrapper::ManuallySet`] and digits
+    /// use their first variant, like when parsing an [`Equation`]
+    /// ```
+    /// # use matchstick::puzzle::Puzzle;
+    /// # use matchstick::puzzle::text_block::TextBlockError;
+    /// let puzzle = Puzzle::from_text_block("riddle: 3=8\nmoves: 1").unwrap();
+    /// assert_eq!(&1, puzzle.get_riddle().get_number_matchstick_movements());
+    ///
+    /// assert_eq!(
+    ///     Err(TextBlockError::InvalidMoves("one".to_string())),
+    ///     Puzzle::from_text_block("riddle: 3=8\nmoves: one")
+    /// );
+    /// ```
+    pub fn from_text_block(text_block: &str) -> Result<Self, TextBlockError> {
+        let mut lines = text_block.lines().map(str::trim);
+
+        let riddle_equation: Equation = lines
+            .next()
+            .and_then(|line| line.strip_prefix(RIDDLE_PREFIX))
+            .ok_or(TextBlockError::MissingRiddle)?
+            .parse()?;
+        let moves = lines
+            .next()
+            .and_then(|line| line.strip_prefix(MOVES_PREFIX))
+            .ok_or(TextBlockError::MissingMoves)?;
+        let number_matchstick_movements = moves
+            .parse()
+            .map_err(|_| TextBlockError::InvalidMoves(moves.to_string()))?;
+
+        let mut puzzle =
+            Puzzle::new_from_riddle(Riddle::new(riddle_equation, number_matchstick_movements));
+
+        match lines.next() {
+            None => {}
+            Some(SOLUTIONS_HEADER) => {
+                let solution_equations = lines
+                    .map(|line| line.parse())
+                    .collect::<Result<Vec<Equation>, _>>()?;
+                puzzle.wrapped_solution =
+                    SolutionWrapper::new_manually_set_solution(solution_equations);
+            }
+            Some(line) => return Err(TextBlockError::UnexpectedLine(line.to_string())),
+        }
+
+        Ok(puzzle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_solved_puzzle() {
+        let puzzle = Puzzle::solved_from_riddle(Riddle::new("6+4=4".parse().unwrap(), 1));
+        let read_puzzle = Puzzle::from_text_block(&puzzle.to_text_block()).unwrap();
+
+        assert_eq!(puzzle.riddle, read_puzzle.riddle);
+        assert_eq!(
+            puzzle.wrapped_solution.get_inner_reference(),
+            read_puzzle.wrapped_solution.get_inner_reference()
+        );
+        assert_eq!(puzzle.to_text_block(), read_puzzle.to_text_block());
+    }
+
+    #[test]
+    fn round_trip_puzzle_without_solutions() {
+        let mut puzzle = Puzzle::new_from_riddle(Riddle::new("3=8".parse().unwrap(), 2));
+        assert_eq!("riddle: 3=8\nmoves: 2", puzzle.to_text_block());
+
+        puzzle.manually_set_solution(SolutionWrapper::new_manually_set_solution(vec![]));
+        assert_eq!("riddle: 3=8\nmoves: 2\nsolutions:", puzzle.to_text_block());
+        assert_eq!(
+            Ok(puzzle),
+            Puzzle::from_text_block("riddle: 3=8\nmoves: 2\nsolutions:")
+        );
+    }
+
+    #[test]
+    fn reject_unexpected_line() {
+        assert_eq!(
+            Err(TextBlockError::UnexpectedLine("1+3=4".to_string())),
+            Puzzle::from_text_block("riddle: 7-3=4\nmoves: 1\n1+3=4")
+        );
+        assert_eq!(
+            Err(TextBlockError::MissingRiddle),
+            Puzzle::from_text_block("moves: 1")
+        );
+    }
+}
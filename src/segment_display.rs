@@ -1,50 +1,205 @@
 use crate::transition::Transition;
 
 macro_rules! delta_for_segment_display {
-    ($($position:ident,)*) => {
+    ($($position:ident $segment:ident,)*) => {
         /// A representation of a digital display used for numbers \
         /// Similar to a 7-segment display
         /// A segment can light up (```true```) or be turned of (```false```).
+        #[derive(Clone, Debug, PartialEq)]
         pub struct SegmentDisplay {
             $(
                 pub $position: bool,
             )*
         }
 
-        impl SegmentDisplay {
-            // Shows how many segments need to change state (s. [`Transition`])
-            // to reach another [`SegmentDisplay`]
-            pub(crate) fn delta_to(&self, target: &Self) -> Transition {
-                let mut remove = 0;
-                let mut add = 0;
+        /// Names the positions of the segments of a [`SegmentDisplay`]
+        #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+        pub enum Segment {
+            $(
+                $segment,
+            )*
+        }
 
+        impl Segment {
+            // All segments without allocating, e.g. for comparing segment displays
+            pub(crate) const ALL: &'static [Segment] = &[
                 $(
-                    if self.$position != target.$position {
-                        match target.$position {
-                            true => add += 1,
-                            false => remove += 1,
-                        }
-                    }
+                    Segment::$segment,
                 )*
+            ];
 
-                Transition { remove, add }
+            /// All [`Segment`]s of a [`SegmentDisplay`]
+            pub fn get_all() -> Vec<Self> {
+                Self::ALL.to_vec()
+            }
+        }
+
+        impl SegmentDisplay {
+            /// Whether the [`Segment`] lights up
+            /// ```
+            /// # use matchstick::segment_display::Segment;
+            /// # use matchstick::symbol::Symbol;
+            /// let seven = Symbol::Seven.to_segment_display();
+            /// assert!(seven.is_lit(Segment::Top));
+            /// assert!(!seven.is_lit(Segment::Bottom));
+            /// ```
+            pub fn is_lit(&self, segment: Segment) -> bool {
+                match segment {
+                    $(
+                        Segment::$segment => self.$position,
+                    )*
+                }
+            }
+
+            /// Lights up the [`Segment`] (```true```) or turns it off (```false```)
+            /// ```
+            /// # use matchstick::segment_display::Segment;
+            /// # use matchstick::symbol::Symbol;
+            /// let mut segment_display = Symbol::OneVar1.to_segment_display();
+            /// segment_display.set(Segment::Top, true);
+            /// assert_eq!(Symbol::Seven.to_segment_display(), segment_display);
+            /// ```
+            pub fn set(&mut self, segment: Segment, is_lit: bool) {
+                match segment {
+                    $(
+                        Segment::$segment => self.$position = is_lit,
+                    )*
+                }
+            }
+
+            /// Segments lit in either [`SegmentDisplay`]
+            /// ```
+            /// # use matchstick::symbol::Symbol;
+            /// let minus = Symbol::Minus.to_segment_display();
+            /// let plus = minus.union(&Symbol::Plus.to_segment_display());
+            /// assert_eq!(Symbol::Plus.to_segment_display(), plus);
+            /// ```
+            pub fn union(&self, other: &Self) -> Self {
+                SegmentDisplay {
+                    $(
+                        $position: self.$position || other.$position,
+                    )*
+                }
+            }
+
+            /// Segments lit in both [`SegmentDisplay`]s, i.e. matchsticks they share
+            /// ```
+            /// # use matchstick::symbol::Symbol;
+            /// let seven = Symbol::Seven.to_segment_display();
+            /// let shared = seven.intersection(&Symbol::OneVar1.to_segment_display());
+            /// assert_eq!(Symbol::OneVar1.to_segment_display(), shared);
+            /// ```
+            pub fn intersection(&self, other: &Self) -> Self {
+                SegmentDisplay {
+                    $(
+                        $position: self.$position && other.$position,
+                    )*
+                }
+            }
+
+            /// Segments lit in this [`SegmentDisplay`] but not in the other one \
+            /// E.g. the segments that must be added to turn a five into an eight
+            /// ```
+            /// # use matchstick::segment_display::Segment;
+            /// # use matchstick::symbol::Symbol;
+            /// let eight = Symbol::EightVar1.to_segment_display();
+            /// let added = eight.difference(&Symbol::Five.to_segment_display());
+            /// assert_eq!(
+            ///     vec![Segment::UpperRight, Segment::LowerLeft],
+            ///     Segment::get_all()
+            ///         .into_iter()
+            ///         .filter(|segment| added.is_lit(*segment))
+            ///         .collect::<Vec<_>>()
+            /// );
+            /// ```
+            pub fn difference(&self, other: &Self) -> Self {
+                SegmentDisplay {
+                    $(
+                        $position: self.$position && !other.$position,
+                    )*
+                }
             }
         }
     };
 }
 delta_for_segment_display!(
-    top,
-    upper_left,
-    upper_right,
-    upper_beam,
-    middle_beam,
-    pipe,
-    lower_left,
-    lower_right,
-    bottom,
+    top Top,
+    upper_left UpperLeft,
+    upper_right UpperRight,
+    upper_beam UpperBeam,
+    middle_beam MiddleBeam,
+    pipe Pipe,
+    lower_left LowerLeft,
+    lower_right LowerRight,
+    bottom Bottom,
 );
 
 impl SegmentDisplay {
+    // Shows how many segments need to change state (s. [`Transition`])
+    // to reach another [`SegmentDisplay`]
+    pub(crate) fn delta_to(&self, target: &Self) -> Transition {
+        let mut transition = Transition::default();
+
+        // this is called for every symbol variant while moving matchsticks,
+        // so the segments are counted without collecting them
+        for segment in Segment::ALL {
+            match (self.is_lit(*segment), target.is_lit(*segment)) {
+                (true, false) => transition.remove += 1,
+                (false, true) => transition.add += 1,
+                _ => {}
+            }
+        }
+
+        transition
+    }
+
+    /// Shows which [`Segment`]s need to be removed and which need to be added
+    /// to reach another [`SegmentDisplay`]
+    /// ```
+    /// # use matchstick::segment_display::Segment;
+    /// # use matchstick::symbol::Symbol;
+    /// let five = Symbol::Five.to_segment_display();
+    /// let three = Symbol::Three.to_segment_display();
+    ///
+    /// assert_eq!(
+    ///     (vec![Segment::UpperLeft], vec![Segment::UpperRight]),
+    ///     five.changed_segments(&three)
+    /// );
+    /// ```
+    pub fn changed_segments(&self, target: &Self) -> (Vec<Segment>, Vec<Segment>) {
+        Segment::ALL
+            .iter()
+            .copied()
+            .filter(|segment| self.is_lit(*segment) != target.is_lit(*segment))
+            .partition(|segment| !target.is_lit(*segment))
+    }
+
+    /// The [`SegmentDisplay`] flipped from left to right
+    pub fn mirror_horizontal(&self) -> Self {
+        SegmentDisplay {
+            upper_left: self.upper_right,
+            upper_right: self.upper_left,
+            lower_left: self.lower_right,
+            lower_right: self.lower_left,
+            ..self.clone()
+        }
+    }
+
+    /// The [`SegmentDisplay`] turned upside down by 180 degrees \
+    /// Outer segments swap with their opposite ones, the middle beam stays.
+    /// Upper beam and pipe only belong to operators and the degree sign and are kept in place
+    pub fn rotate_180(&self) -> Self {
+        SegmentDisplay {
+            top: self.bottom,
+            upper_left: self.lower_right,
+            upper_right: self.lower_left,
+            lower_left: self.upper_right,
+            lower_right: self.upper_left,
+            bottom: self.top,
+            ..self.clone()
+        }
+    }
+
     /// The segments can be visualized with five string lines
     /// ```text
     /// 1  ___
@@ -147,11 +302,115 @@ impl SegmentDisplay {
 
         segment_display
     }
+
+    /// Like [`SegmentDisplay::draw`] but enlarged by the given scale \
+    /// Horizontal segments get `scale` characters on each side of the center column
+    /// and vertical segments span `2 * scale` lines in each half, so the drawing is
+    /// `4 * scale + 1` lines high and `2 * scale + 3` characters wide. A scale of 0 is treated as 1
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(Symbol::FourVar1.to_segment_display().draw_scaled(2),
+    #[doc = "\"       "]
+    #[doc = "|     |"]
+    #[doc = "|     |"]
+    #[doc = "|     |"]
+    #[doc = "|__ __|"]
+    #[doc = "      |"]
+    #[doc = "      |"]
+    #[doc = "      |"]
+    #[doc = "      |\");"]
+    /// ```
+    pub fn draw_scaled(&self, scale: usize) -> String {
+        let scale = scale.max(1);
+        let lines_per_half = 2 * scale;
+
+        // a horizontal segment is drawn as underscores, otherwise as spaces
+        let horizontal = |is_lit: bool, length: usize| match is_lit {
+            true => "_".repeat(length),
+            false => " ".repeat(length),
+        };
+        let vertical = |is_lit: bool| match is_lit {
+            true => '|',
+            false => ' ',
+        };
+
+        let mut lines = Vec::new();
+
+        // top segment above the vertical segments
+        lines.push(format!(" {} ", horizontal(self.top, 2 * scale + 1)));
+
+        // upper half
+        // the pipe reaches into the last lines, ending at the middle beam
+        for line_index in 0..lines_per_half {
+            let beam = match line_index {
+                0 => self.upper_beam,
+                _ if line_index == lines_per_half - 1 => self.middle_beam,
+                _ => false,
+            };
+            let pipe = self.pipe && line_index >= scale;
+
+            lines.push(format!(
+                "{}{}{}{}{}",
+                vertical(self.upper_left),
+                horizontal(beam, scale),
+                vertical(pipe),
+                horizontal(beam, scale),
+                vertical(self.upper_right),
+            ));
+        }
+
+        // lower half
+        // the pipe reaches into the first lines, starting at the middle beam
+        for line_index in 0..lines_per_half {
+            let center = match line_index == lines_per_half - 1 {
+                true => horizontal(self.bottom, 2 * scale + 1),
+                false => format!(
+                    "{}{}{}",
+                    " ".repeat(scale),
+                    vertical(self.pipe && line_index < scale),
+                    " ".repeat(scale)
+                ),
+            };
+
+            lines.push(format!(
+                "{}{}{}",
+                vertical(self.lower_left),
+                center,
+                vertical(self.lower_right),
+            ));
+        }
+
+        lines.join("\n")
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::symbol::Symbol;
+
+    #[test]
+    fn combine_segment_displays() {
+        let five = Symbol::Five.to_segment_display();
+        let three = Symbol::Three.to_segment_display();
+
+        assert_eq!(Symbol::Nine.to_segment_display(), five.union(&three));
+        // the union is made up of the shared segments and both differences
+        assert_eq!(
+            five.union(&three),
+            five.intersection(&three)
+                .union(&five.difference(&three))
+                .union(&three.difference(&five))
+        );
+        let removed = five.difference(&three);
+        assert_eq!(
+            five.delta_to(&three).remove,
+            Segment::ALL
+                .iter()
+                .filter(|segment| removed.is_lit(**segment))
+                .count()
+        );
+    }
 
     #[test]
     fn transition_no_to_all_segments() {
@@ -246,6 +505,67 @@ mod tests {
         assert_eq!(expected_transition, start.delta_to(&target));
     }
 
+    #[test]
+    fn changed_mixed_segments() {
+        let start = SegmentDisplay {
+            top: false,
+            upper_left: true,
+            upper_right: true,
+            upper_beam: false,
+            middle_beam: false,
+            pipe: false,
+            lower_left: false,
+            lower_right: true,
+            bottom: false,
+        };
+
+        let target = SegmentDisplay {
+            top: false,
+            upper_left: true,
+            upper_right: false,
+            upper_beam: false,
+            middle_beam: true,
+            pipe: true,
+            lower_left: false,
+            lower_right: true,
+            bottom: false,
+        };
+
+        let expected_removed_segments = vec![Segment::UpperRight];
+        let expected_added_segments = vec![Segment::MiddleBeam, Segment::Pipe];
+
+        assert_eq!(
+            (expected_removed_segments, expected_added_segments),
+            start.changed_segments(&target)
+        );
+    }
+
+    #[test]
+    fn set_each_segment() {
+        let all_segments = SegmentDisplay {
+            top: true,
+            upper_left: true,
+            upper_right: true,
+            upper_beam: true,
+            middle_beam: true,
+            pipe: true,
+            lower_left: true,
+            lower_right: true,
+            bottom: true,
+        };
+
+        for segment in Segment::get_all() {
+            let mut segment_display = all_segments.clone();
+            segment_display.set(segment, false);
+
+            assert!(!segment_display.is_lit(segment));
+            assert_eq!(
+                (vec![segment], vec![]),
+                all_segments.changed_segments(&segment_display)
+            );
+        }
+    }
+
     #[test]
     fn draw_no_segment() {
         let segment_display = SegmentDisplay {
@@ -311,4 +631,42 @@ mod tests {
      ";
         assert_eq!(segment_display.draw(), expected_string);
     }
+
+    #[test]
+    fn draw_scaled_once_like_draw() {
+        for symbol in crate::symbol::Symbol::get_all() {
+            let segment_display = symbol.to_segment_display();
+            assert_eq!(segment_display.draw(), segment_display.draw_scaled(1));
+        }
+    }
+
+    #[test]
+    fn draw_all_segments_scaled_thrice() {
+        let segment_display = SegmentDisplay {
+            top: true,
+            upper_left: true,
+            upper_right: true,
+            upper_beam: true,
+            middle_beam: true,
+            pipe: true,
+            lower_left: true,
+            lower_right: true,
+            bottom: true,
+        };
+
+        let expected_string = " _______ 
+|___ ___|
+|       |
+|       |
+|   |   |
+|   |   |
+|___|___|
+|   |   |
+|   |   |
+|   |   |
+|       |
+|       |
+|_______|";
+        assert_eq!(segment_display.draw_scaled(3), expected_string);
+    }
 }
@@ -1,4 +1,4 @@
-use crate::segment_display::SegmentDisplay;
+use crate::segment_display::{Segment, SegmentDisplay};
 use crate::transition::Transition;
 
 /// Filters for [`Symbol`]s with specific characteristics, such as being a number or an operator
@@ -6,16 +6,63 @@ use crate::transition::Transition;
 pub enum SymbolFilter {
     IsAny,
     IsNumber,
+    IsNonZeroNumber,
     IsOperator,
+    IsGrouping,
+    IsDecorative,
     List(Vec<Symbol>),
 }
 
 impl SymbolFilter {
+    /// Filters of a negative number operand with the given number of digits,
+    /// i.e. a [`Symbol::Minus`] read as negation followed by the digits \
+    /// The first digit is never zero, so neither "-0" nor "-05" is formed. \
+    /// A negation is only valid at the start of an expression, e.g. in "-3+5=2"
+    /// ```
+    /// # use matchstick::equation::EquationPattern;
+    /// # use matchstick::symbol::{Symbol, SymbolFilter};
+    /// assert_eq!(
+    ///     vec![
+    ///         SymbolFilter::List(vec![Symbol::Minus]),
+    ///         SymbolFilter::IsNonZeroNumber,
+    ///         SymbolFilter::IsNumber,
+    ///     ],
+    ///     SymbolFilter::negative_number(2)
+    /// );
+    ///
+    /// // "-N+N=N" where the first operand is negative
+    /// let equation_pattern = EquationPattern::new_from_symbol_filters(
+    ///     [
+    ///         SymbolFilter::negative_number(1),
+    ///         "+N=N".parse::<EquationPattern>().unwrap().into_symbol_filters(),
+    ///     ]
+    ///     .concat(),
+    /// );
+    /// assert_eq!("-[123456789]+N=N", equation_pattern.to_pattern_string());
+    /// ```
+    pub fn negative_number(number_digits: usize) -> Vec<Self> {
+        let mut symbol_filters = vec![SymbolFilter::List(vec![Symbol::Minus])];
+        if number_digits > 0 {
+            symbol_filters.push(SymbolFilter::IsNonZeroNumber);
+            symbol_filters.extend(vec![SymbolFilter::IsNumber; number_digits - 1]);
+        }
+
+        symbol_filters
+    }
+
     pub(crate) fn get_corresponding_symbols(&self) -> Vec<Symbol> {
         match self {
             SymbolFilter::IsAny => Symbol::get_all(),
             SymbolFilter::List(symbols) => symbols.clone(),
-            SymbolFilter::IsNumber | SymbolFilter::IsOperator => {
+            SymbolFilter::IsNonZeroNumber => SymbolFilter::IsNumber
+                .get_corresponding_symbols()
+                .into_iter()
+                .filter(|symbol| *symbol != Symbol::Zero)
+                .collect(),
+            SymbolFilter::IsNumber
+            | SymbolFilter::IsOperator
+            | SymbolFilter::IsGrouping
+            | SymbolFilter::IsDecorative => {
                 let mut list_symbols = Vec::new();
                 for symbol in Symbol::get_all() {
                     let symbol_filter_type = match symbol {
@@ -35,6 +82,9 @@ impl SymbolFilter {
                         Symbol::EightVar2 => SymbolFilter::IsNumber,
                         Symbol::Nine => SymbolFilter::IsNumber,
                         Symbol::Zero => SymbolFilter::IsNumber,
+                        Symbol::ParenOpen => SymbolFilter::IsGrouping,
+                        Symbol::ParenClose => SymbolFilter::IsGrouping,
+                        Symbol::Degree => SymbolFilter::IsDecorative,
                     };
                     if self == &symbol_filter_type {
                         list_symbols.push(symbol);
@@ -49,8 +99,9 @@ impl SymbolFilter {
 macro_rules! impl_symbols {
     ($($variant:ident $display_string:expr, $drawn_string:expr, ($($position:ident: $value:expr, )*),)*) => {
         /// A specific, meaningful constellation of matchsticks
-        /// showing a mathematical "character", like a digit or an operator
-        #[derive(Clone, Debug, Eq, PartialEq)]
+        /// showing a mathematical "character", like a digit or an operator \
+        /// [`Symbol`]s are ordered as they are declared
+        #[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
         pub enum Symbol {
             $(
                 #[doc = concat!("The mathematical value is \"", $display_string, "\" and the segment representation is\n```text\n", $drawn_string)]
@@ -99,7 +150,38 @@ macro_rules! impl_symbols {
                 segment_display.draw()
             }
 
-            fn to_segment_display(&self) -> SegmentDisplay {
+            /// Checks that the table of [`Symbol`]s is consistent, e.g. after adding new symbols \
+            /// Returns the symbols whose drawing differs from the documented drawing
+            /// or whose [`SegmentDisplay`] is shared with another symbol
+            /// ```
+            /// # use matchstick::symbol::Symbol;
+            /// assert_eq!(Ok(()), Symbol::verify_all());
+            /// ```
+            pub fn verify_all() -> Result<(), Vec<Self>> {
+                let mut inconsistent_symbols = Vec::new();
+
+                $(
+                    let symbol = Symbol::$variant;
+                    let number_equal_segment_displays = Self::get_all()
+                        .iter()
+                        .filter(|other_symbol| {
+                            other_symbol.to_segment_display() == symbol.to_segment_display()
+                        })
+                        .count();
+
+                    if symbol.draw() != $drawn_string || number_equal_segment_displays != 1 {
+                        inconsistent_symbols.push(symbol);
+                    }
+                )*
+
+                match inconsistent_symbols.is_empty() {
+                    true => Ok(()),
+                    false => Err(inconsistent_symbols),
+                }
+            }
+
+            /// The [`SegmentDisplay`] showing which segments of the [`Symbol`] light up
+            pub fn to_segment_display(&self) -> SegmentDisplay {
                 match self {
                     $(
                         Symbol::$variant => SegmentDisplay {
@@ -114,6 +196,7 @@ macro_rules! impl_symbols {
             // Gets all valid [`Symbol`]s that emerged when adding/removing segments
             // of the original [`Symbol`] according to given matchstick movements
             // E.g. when adding one segment to Five, it results in either Six or Nine
+            // The symbols are returned in declaration order, so Six comes before Nine
             //            ___
             //           |    __________
             //           |_ _          |
@@ -336,12 +419,275 @@ impl_symbols!(
         lower_right: true,
         bottom: true,
     ),
+    ParenOpen "(", " ___ \n|    \n|    \n|    \n|___ ", (
+        top: true,
+        upper_left: true,
+        upper_right: false,
+        middle_beam: false,
+        upper_beam: false,
+        pipe: false,
+        lower_left: true,
+        lower_right: false,
+        bottom: true,
+    ),
+    ParenClose ")", " ___ \n    |\n    |\n    |\n ___|", (
+        top: true,
+        upper_left: false,
+        upper_right: true,
+        middle_beam: false,
+        upper_beam: false,
+        pipe: false,
+        lower_left: false,
+        lower_right: true,
+        bottom: true,
+    ),
+    Degree "°", " ___ \n|_ _|\n|   |\n     \n     ", (
+        top: true,
+        upper_left: true,
+        upper_right: true,
+        middle_beam: false,
+        upper_beam: true,
+        pipe: false,
+        lower_left: false,
+        lower_right: false,
+        bottom: false,
+    ),
 );
 
+impl Symbol {
+    // Whether the symbol is a digit
+    pub(crate) fn is_number(&self) -> bool {
+        SymbolFilter::IsNumber
+            .get_corresponding_symbols()
+            .contains(self)
+    }
+
+    // Whether the symbol is an operator, including the equal sign
+    pub(crate) fn is_operator(&self) -> bool {
+        SymbolFilter::IsOperator
+            .get_corresponding_symbols()
+            .contains(self)
+    }
+
+    // Whether the symbol groups parts of an expression, like parentheses
+    pub(crate) fn is_grouping(&self) -> bool {
+        SymbolFilter::IsGrouping
+            .get_corresponding_symbols()
+            .contains(self)
+    }
+
+    // Whether the symbol only decorates the equation, like a unit after a number
+    pub(crate) fn is_decorative(&self) -> bool {
+        SymbolFilter::IsDecorative
+            .get_corresponding_symbols()
+            .contains(self)
+    }
+
+    // Whether the digit is shown in its alternative variant, e.g. [`Symbol::FourVar2`]
+    pub(crate) fn is_alternative_variant(&self) -> bool {
+        matches!(self, Symbol::OneVar2 | Symbol::FourVar2 | Symbol::EightVar2)
+    }
+
+    /// A compact single-line glyph of the [`Symbol`] for dense output like logs \
+    /// Digits use the seven-segment digits of Unicode's "Symbols for Legacy Computing",
+    /// operators and parentheses their full-width forms. Variants of a digit share the same glyph
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!("🯷", Symbol::Seven.draw_inline());
+    /// assert_eq!("＋", Symbol::Plus.draw_inline());
+    /// ```
+    pub fn draw_inline(&self) -> &str {
+        match self {
+            Symbol::Minus => "－",
+            Symbol::Plus => "＋",
+            Symbol::Equal => "＝",
+            Symbol::OneVar1 | Symbol::OneVar2 => "🯱",
+            Symbol::Two => "🯲",
+            Symbol::Three => "🯳",
+            Symbol::FourVar1 | Symbol::FourVar2 => "🯴",
+            Symbol::Five => "🯵",
+            Symbol::Six => "🯶",
+            Symbol::Seven => "🯷",
+            Symbol::EightVar1 | Symbol::EightVar2 => "🯸",
+            Symbol::Nine => "🯹",
+            Symbol::Zero => "🯰",
+            Symbol::ParenOpen => "（",
+            Symbol::ParenClose => "）",
+            Symbol::Degree => "°",
+        }
+    }
+
+    /// Whether both symbols represent the same mathematical character
+    /// regardless of their variant
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert!(Symbol::FourVar1.same_value(&Symbol::FourVar2));
+    /// assert!(!Symbol::FourVar1.same_value(&Symbol::Nine));
+    /// ```
+    pub fn same_value(&self, other: &Self) -> bool {
+        self.to_str() == other.to_str()
+    }
+
+    /// Number of matchsticks the [`Symbol`] is built from, i.e. its lit segments
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(2, Symbol::OneVar1.matchstick_count());
+    /// assert_eq!(7, Symbol::EightVar1.matchstick_count());
+    /// ```
+    pub fn matchstick_count(&self) -> usize {
+        let segment_display = self.to_segment_display();
+
+        Segment::get_all()
+            .into_iter()
+            .filter(|segment| segment_display.is_lit(*segment))
+            .count()
+    }
+
+    // Like [`Symbol::apply_transition`] but grouping and decorative symbols are only formed
+    // if allowed, so moving matchsticks doesn't turn digits into parentheses or degree signs
+    // unless asked for
+    pub(crate) fn apply_transition_allowing(
+        &self,
+        transition: Transition,
+        allow_grouping: bool,
+        allow_decorative: bool,
+    ) -> Vec<Self> {
+        let mut symbols = self.apply_transition(transition);
+        symbols.retain(|symbol| {
+            (allow_grouping || !symbol.is_grouping())
+                && (allow_decorative || !symbol.is_decorative())
+        });
+        symbols
+    }
+
+    /// All symbols that can be formed by moving exactly one matchstick
+    /// within this symbol, i.e. removing one segment and adding another
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(
+    ///     vec![Symbol::Nine, Symbol::Zero],
+    ///     Symbol::Six.reachable_in_one_internal_move()
+    /// );
+    /// ```
+    pub fn reachable_in_one_internal_move(&self) -> Vec<Self> {
+        self.apply_transition(Transition { remove: 1, add: 1 })
+    }
+
+    /// All symbols that can be formed by adding exactly one matchstick
+    /// to this symbol without removing any
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(
+    ///     vec![Symbol::Six, Symbol::Nine],
+    ///     Symbol::Five.reachable_by_adding_one()
+    /// );
+    /// ```
+    pub fn reachable_by_adding_one(&self) -> Vec<Self> {
+        self.apply_transition(Transition { remove: 0, add: 1 })
+    }
+
+    /// The [`Symbol`] shown when flipping this symbol from left to right \
+    /// `None` if the flipped segments don't form any symbol
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(Some(Symbol::OneVar2), Symbol::OneVar1.mirror_horizontal());
+    /// assert_eq!(None, Symbol::Seven.mirror_horizontal());
+    /// ```
+    pub fn mirror_horizontal(&self) -> Option<Self> {
+        let mirrored_segment_display = self.to_segment_display().mirror_horizontal();
+
+        Self::get_all()
+            .into_iter()
+            .find(|symbol| symbol.to_segment_display() == mirrored_segment_display)
+    }
+
+    /// The [`Symbol`] shown when turning this symbol upside down by 180 degrees \
+    /// Plus and equal sign are drawn slightly off-center, but keep their shape
+    /// like real matchsticks do. `None` if the turned segments don't form any symbol
+    /// ```
+    /// # use matchstick::symbol::Symbol;
+    /// assert_eq!(Some(Symbol::Nine), Symbol::Six.rotate_180());
+    /// assert_eq!(Some(Symbol::OneVar2), Symbol::OneVar1.rotate_180());
+    /// assert_eq!(None, Symbol::Seven.rotate_180());
+    /// ```
+    pub fn rotate_180(&self) -> Option<Self> {
+        if matches!(self, Symbol::Plus | Symbol::Equal) {
+            return Some(self.clone());
+        }
+        let rotated_segment_display = self.to_segment_display().rotate_180();
+
+        Self::from_segment_display(&rotated_segment_display)
+    }
+
+    /// The [`Symbol`] whose segments are lit exactly like in the [`SegmentDisplay`] \
+    /// Returns `None` if the [`SegmentDisplay`] doesn't show any [`Symbol`],
+    /// e.g. to check a hand-built one
+    /// ```
+    /// # use matchstick::segment_display::Segment;
+    /// # use matchstick::symbol::Symbol;
+    /// let mut segment_display = Symbol::OneVar1.to_segment_display();
+    /// segment_display.set(Segment::Top, true);
+    /// assert_eq!(Some(Symbol::Seven), Symbol::from_segment_display(&segment_display));
+    ///
+    /// segment_display.set(Segment::LowerLeft, true);
+    /// assert_eq!(None, Symbol::from_segment_display(&segment_display));
+    /// ```
+    pub fn from_segment_display(segment_display: &SegmentDisplay) -> Option<Self> {
+        Self::get_all()
+            .into_iter()
+            .find(|symbol| symbol.to_segment_display() == *segment_display)
+    }
+
+    // Shows how many segments need to change state (s. [`Transition`])
+    // to reach another [`Symbol`]
+    pub(crate) fn delta_to(&self, target: &Self) -> Transition {
+        self.to_segment_display()
+            .delta_to(&target.to_segment_display())
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
 
+    #[test]
+    fn five_becomes_six_or_nine_by_adding_one() {
+        // the added matchstick is either the lower left or the upper right segment
+        assert_eq!(
+            vec![Symbol::Six, Symbol::Nine],
+            Symbol::Five.apply_transition(Transition { remove: 0, add: 1 })
+        );
+        assert_eq!(
+            Transition { remove: 0, add: 1 },
+            Symbol::Five.delta_to(&Symbol::Six)
+        );
+        assert_eq!(
+            Transition { remove: 0, add: 1 },
+            Symbol::Five.delta_to(&Symbol::Nine)
+        );
+    }
+
+    #[test]
+    fn six_and_nine_become_five_by_removing_one() {
+        for symbol in [Symbol::Six, Symbol::Nine] {
+            assert!(
+                symbol
+                    .apply_transition(Transition { remove: 1, add: 0 })
+                    .contains(&Symbol::Five)
+            );
+        }
+    }
+
+    #[test]
+    fn find_each_symbol_by_its_segment_display() {
+        for symbol in Symbol::get_all() {
+            assert_eq!(
+                Some(symbol.clone()),
+                Symbol::from_segment_display(&symbol.to_segment_display())
+            );
+        }
+    }
+
     #[test]
     fn get_numbers_symbols() {
         let number_symbols = vec![
@@ -366,6 +712,29 @@ mod test {
         );
     }
 
+    #[test]
+    fn get_non_zero_numbers_symbols() {
+        let non_zero_number_symbols = vec![
+            Symbol::OneVar1,
+            Symbol::OneVar2,
+            Symbol::Two,
+            Symbol::Three,
+            Symbol::FourVar1,
+            Symbol::FourVar2,
+            Symbol::Five,
+            Symbol::Six,
+            Symbol::Seven,
+            Symbol::EightVar1,
+            Symbol::EightVar2,
+            Symbol::Nine,
+        ];
+
+        assert_eq!(
+            non_zero_number_symbols,
+            SymbolFilter::IsNonZeroNumber.get_corresponding_symbols()
+        );
+    }
+
     #[test]
     fn get_no_numbers_symbols() {
         let no_number_symbols = vec![Symbol::Minus, Symbol::Plus, Symbol::Equal];
@@ -376,6 +745,76 @@ mod test {
         );
     }
 
+    #[test]
+    fn add_exactly_one_matchstick() {
+        assert_eq!(
+            vec![Symbol::Plus, Symbol::Equal],
+            Symbol::Minus.reachable_by_adding_one()
+        );
+        assert_eq!(
+            vec![Symbol::Seven],
+            Symbol::OneVar1.reachable_by_adding_one()
+        );
+        assert!(Symbol::EightVar1.reachable_by_adding_one().is_empty());
+    }
+
+    #[test]
+    fn rotate_symbols() {
+        let rotatable_symbols = Symbol::get_all()
+            .into_iter()
+            .filter(|symbol| symbol.rotate_180().is_some())
+            .collect::<Vec<_>>();
+        // the small eight sits in the upper half, so it can't be turned
+        assert_eq!(
+            vec![
+                Symbol::Minus,
+                Symbol::Plus,
+                Symbol::Equal,
+                Symbol::OneVar1,
+                Symbol::OneVar2,
+                Symbol::Two,
+                Symbol::Five,
+                Symbol::Six,
+                Symbol::EightVar1,
+                Symbol::Nine,
+                Symbol::Zero,
+                Symbol::ParenOpen,
+                Symbol::ParenClose,
+            ],
+            rotatable_symbols
+        );
+        // turning twice shows the original symbol again
+        for symbol in rotatable_symbols {
+            assert_eq!(
+                Some(symbol.clone()),
+                symbol.rotate_180().and_then(|rotated| rotated.rotate_180())
+            );
+        }
+    }
+
+    #[test]
+    fn get_grouping_symbols() {
+        assert_eq!(
+            vec![Symbol::ParenOpen, Symbol::ParenClose],
+            SymbolFilter::IsGrouping.get_corresponding_symbols()
+        );
+        assert_eq!(
+            Some(Symbol::ParenClose),
+            Symbol::ParenOpen.mirror_horizontal()
+        );
+    }
+
+    #[test]
+    fn sort_in_declaration_order() {
+        let mut symbols = vec![Symbol::Zero, Symbol::Plus, Symbol::Five, Symbol::Minus];
+        symbols.sort();
+
+        assert_eq!(
+            vec![Symbol::Minus, Symbol::Plus, Symbol::Five, Symbol::Zero],
+            symbols
+        );
+    }
+
     #[test]
     fn symbol_filter_no_restrictions() {
         assert_eq!(
@@ -383,4 +822,29 @@ mod test {
             Symbol::get_all()
         );
     }
+
+    #[test]
+    fn same_value_across_variants() {
+        assert!(Symbol::OneVar1.same_value(&Symbol::OneVar2));
+        assert!(Symbol::FourVar2.same_value(&Symbol::FourVar1));
+        assert!(Symbol::EightVar1.same_value(&Symbol::EightVar2));
+        assert!(Symbol::Plus.same_value(&Symbol::Plus));
+        assert!(!Symbol::OneVar1.same_value(&Symbol::Seven));
+        assert!(!Symbol::Minus.same_value(&Symbol::Equal));
+    }
+
+    #[test]
+    fn no_symbol_reachable_in_one_internal_move() {
+        assert!(Symbol::Seven.reachable_in_one_internal_move().is_empty());
+        assert!(Symbol::Minus.reachable_in_one_internal_move().is_empty());
+    }
+
+    #[test]
+    fn mirror_twice_is_original_symbol() {
+        for symbol in Symbol::get_all() {
+            if let Some(mirrored_symbol) = symbol.mirror_horizontal() {
+                assert_eq!(Some(symbol), mirrored_symbol.mirror_horizontal());
+            }
+        }
+    }
 }
@@ -1,12 +1,49 @@
-// Information on delta between two elements, e.g. [`crate::segment_display::SegmentDisplay`]s
-// or [`crate::symbol::Symbol`]s
+use std::collections::HashMap;
+
+use crate::equation::Equation;
+
+/// Information on delta between two elements, e.g. [`crate::symbol::Symbol`]s \
+/// Tells how many segments are removed and how many are added
 #[derive(Clone, Copy, Debug, Default, PartialEq)]
-pub(crate) struct Transition {
+pub struct Transition {
     pub remove: usize,
     pub add: usize,
 }
 
+/// Readable classification of a [`Transition`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TransitionKind {
+    /// No segment is removed or added
+    NoChange,
+    /// Segments are only added
+    PureAdd,
+    /// Segments are only removed
+    PureRemove,
+    /// As many segments are added as removed, i.e. matchsticks are moved within the element
+    BalancedMove,
+    /// Segments are removed and added, but not as many of each
+    Mixed,
+}
+
 impl Transition {
+    /// What the [`Transition`] does to its element
+    /// ```
+    /// # use matchstick::transition::{Transition, TransitionKind};
+    /// assert_eq!(TransitionKind::NoChange, Transition::default().kind());
+    /// assert_eq!(TransitionKind::PureAdd, Transition { remove: 0, add: 1 }.kind());
+    /// assert_eq!(TransitionKind::BalancedMove, Transition { remove: 2, add: 2 }.kind());
+    /// assert_eq!(TransitionKind::Mixed, Transition { remove: 2, add: 1 }.kind());
+    /// ```
+    pub fn kind(&self) -> TransitionKind {
+        match (self.remove, self.add) {
+            (0, 0) => TransitionKind::NoChange,
+            (0, _) => TransitionKind::PureAdd,
+            (_, 0) => TransitionKind::PureRemove,
+            (remove, add) if remove == add => TransitionKind::BalancedMove,
+            _ => TransitionKind::Mixed,
+        }
+    }
+
     // transition for single element
     fn remove_one(&mut self) {
         self.remove += 1;
@@ -17,9 +54,18 @@ impl Transition {
     }
 }
 
-// Information on delta for several element-pairs, e.g. an [`crate::equation::Equation`]
+/// How the matchsticks are moved between the elements of a [`TransitionSequence`]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum MovePattern {
+    /// Each matchstick is moved within its element, e.g. "6" to "9"
+    WithinSymbols,
+    /// At least one matchstick is moved from one element to another, e.g. "7-" to "1+"
+    BetweenSymbols,
+}
+
+/// Information on delta for several element-pairs, e.g. an [`crate::equation::Equation`]
 #[derive(Clone, Debug, PartialEq)]
-pub(crate) struct TransitionSequence {
+pub struct TransitionSequence {
     pub transitions: Vec<Transition>,
 }
 
@@ -36,6 +82,106 @@ impl TransitionSequence {
         self.transitions.len()
     }
 
+    // How many segments are removed over all element-pairs
+    pub(crate) fn count_removed(&self) -> usize {
+        self.transitions
+            .iter()
+            .map(|transition| transition.remove)
+            .sum()
+    }
+
+    // How many segments are added over all element-pairs
+    pub(crate) fn count_added(&self) -> usize {
+        self.transitions
+            .iter()
+            .map(|transition| transition.add)
+            .sum()
+    }
+
+    // Whether the elements at the given positions keep all their segments
+    // positions outside of the sequence are ignored
+    pub(crate) fn leaves_unchanged(&self, positions: &[usize]) -> bool {
+        positions.iter().all(|position| {
+            self.transitions
+                .get(*position)
+                .is_none_or(|transition| *transition == Transition::default())
+        })
+    }
+
+    /// Whether the matchsticks stay within their elements or move between them \
+    /// Matchsticks stay within their elements if each element gets as many
+    /// segments added as removed
+    /// ```
+    /// # use matchstick::transition::{MovePattern, Transition, TransitionSequence};
+    /// let transition_sequence = TransitionSequence {
+    ///     transitions: vec![Transition { remove: 1, add: 1 }, Transition::default()],
+    /// };
+    /// assert_eq!(MovePattern::WithinSymbols, transition_sequence.move_pattern());
+    ///
+    /// let transition_sequence = TransitionSequence {
+    ///     transitions: vec![Transition { remove: 1, add: 0 }, Transition { remove: 0, add: 1 }],
+    /// };
+    /// assert_eq!(MovePattern::BetweenSymbols, transition_sequence.move_pattern());
+    /// ```
+    pub fn move_pattern(&self) -> MovePattern {
+        if self.transitions.iter().all(|transition| {
+            matches!(
+                transition.kind(),
+                TransitionKind::NoChange | TransitionKind::BalancedMove
+            )
+        }) {
+            MovePattern::WithinSymbols
+        } else {
+            MovePattern::BetweenSymbols
+        }
+    }
+
+    /// Whether the [`TransitionSequence`] can be applied to the given [`Equation`] \
+    /// Each [`Transition`] must turn its symbol into at least one valid symbol, e.g.
+    /// no more matchsticks can be removed than the symbol consists of.
+    /// A single impossible [`Transition`] rules out the whole sequence.
+    /// Solving already skips such sequences while forming equations, so this is meant
+    /// to inspect or count sequences without forming any equation, e.g. to see how many
+    /// of the generated sequences remain for a given equation.
+    /// A sequence of a different length than the equation is never possible
+    /// ```
+    /// # use matchstick::equation::Equation;
+    /// # use matchstick::transition::{Transition, TransitionSequence};
+    /// let equation: Equation = "7-3=4".parse().unwrap();
+    /// let mut transitions = vec![Transition::default(); 5];
+    ///
+    /// // one matchstick from seven onto minus forms "1+3=4"
+    /// transitions[0] = Transition { remove: 1, add: 0 };
+    /// transitions[1] = Transition { remove: 0, add: 1 };
+    /// let transition_sequence = TransitionSequence { transitions: transitions.clone() };
+    /// assert!(transition_sequence.is_physically_possible(&equation));
+    ///
+    /// // minus consists of one matchstick only
+    /// transitions[0] = Transition { remove: 0, add: 2 };
+    /// transitions[1] = Transition { remove: 2, add: 0 };
+    /// let transition_sequence = TransitionSequence { transitions };
+    /// assert!(!transition_sequence.is_physically_possible(&equation));
+    /// ```
+    pub fn is_physically_possible(&self, equation: &Equation) -> bool {
+        let symbols = equation.get_symbols();
+        if symbols.len() != self.transitions.len() {
+            return false;
+        }
+
+        let allow_grouping = equation.allows_grouping();
+        let allow_decorative = equation.allows_decorations();
+        symbols
+            .iter()
+            .zip(&self.transitions)
+            .all(|(symbol, transition)| {
+                // cheap check on the number of matchsticks before comparing segments
+                transition.remove <= symbol.matchstick_count()
+                    && !symbol
+                        .apply_transition_allowing(*transition, allow_grouping, allow_decorative)
+                        .is_empty()
+            })
+    }
+
     fn move_one(&self) -> Vec<Self> {
         let mut new_transition_sequences = Vec::new();
         for source_index in 0..self.get_number_of_transitions() {
@@ -62,6 +208,16 @@ impl TransitionSequence {
         Self::move_n_recursive(number_movements, default_transition_sequence)
     }
 
+    // How many movement patterns [`TransitionSequence::move_n`] generates
+    // Each movement picks a source and a target element
+    // None if the number doesn't fit into usize
+    pub(crate) fn count_move_n(number_movements: usize, number_elements: usize) -> Option<usize> {
+        let number_movements = u32::try_from(number_movements).ok()?;
+        number_elements
+            .checked_mul(number_elements)?
+            .checked_pow(number_movements)
+    }
+
     fn move_n_recursive(number_movements: usize, transition_sequences: Vec<Self>) -> Vec<Self> {
         if number_movements == 0 {
             // no movements allowed anymore
@@ -79,6 +235,27 @@ impl TransitionSequence {
     }
 }
 
+/// Remembers the [`TransitionSequence`]s generated by [`TransitionSequence::move_n`] \
+/// They only depend on the number of movements and elements,
+/// so they can be shared between equations of the same length
+#[derive(Debug, Default)]
+pub(crate) struct TransitionSequenceCache {
+    transition_sequences: HashMap<(usize, usize), Vec<TransitionSequence>>,
+}
+
+impl TransitionSequenceCache {
+    // Like [`TransitionSequence::move_n`] but each combination is only generated once
+    pub(crate) fn move_n(
+        &mut self,
+        number_movements: usize,
+        number_elements: usize,
+    ) -> &[TransitionSequence] {
+        self.transition_sequences
+            .entry((number_movements, number_elements))
+            .or_insert_with(|| TransitionSequence::move_n(number_movements, number_elements))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -218,6 +395,48 @@ mod tests {
         assert_eq!(expected_transition_sequence, transition_sequence.move_one());
     }
 
+    #[test]
+    fn classify_transitions() {
+        assert_eq!(
+            TransitionKind::PureRemove,
+            Transition { remove: 3, add: 0 }.kind()
+        );
+        assert_eq!(
+            TransitionKind::BalancedMove,
+            Transition { remove: 1, add: 1 }.kind()
+        );
+        assert_eq!(
+            TransitionKind::Mixed,
+            Transition { remove: 1, add: 3 }.kind()
+        );
+    }
+
+    #[test]
+    fn detect_impossible_transitions() {
+        let equation: Equation = "1+1=2".parse().unwrap();
+        let possible_sequences: Vec<TransitionSequence> = TransitionSequence::move_n(1, 5)
+            .into_iter()
+            .filter(|transition_sequence| transition_sequence.is_physically_possible(&equation))
+            .collect();
+
+        // only 8 of the 25 ways to move one matchstick leave valid symbols
+        assert_eq!(8, possible_sequences.len());
+        assert!(
+            !TransitionSequence::with_n_default_transitions(4).is_physically_possible(&equation)
+        );
+    }
+
+    #[test]
+    fn count_matches_generated_sequences() {
+        for (number_movements, number_elements) in [(0, 3), (1, 3), (2, 2), (3, 2), (2, 4)] {
+            assert_eq!(
+                Some(TransitionSequence::move_n(number_movements, number_elements).len()),
+                TransitionSequence::count_move_n(number_movements, number_elements)
+            );
+        }
+        assert_eq!(None, TransitionSequence::count_move_n(20, 100));
+    }
+
     #[test]
     fn move_two_in_two_symbols_from_default() {
         let default_transition_sequence = vec![TransitionSequence::with_n_default_transitions(2)];